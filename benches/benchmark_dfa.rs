@@ -0,0 +1,75 @@
+use std::time::{Duration, Instant};
+use std::fs::File;
+use std::io::{Write, BufWriter};
+use rusty_dfa::automata::{Automaton, Dfa};
+use rusty_dfa::parse::parse;
+
+// `cargo bench` runs all of these, each one dumping its results to `benches/results`
+
+fn main() {
+    benchmark_dfa_klenee(20000, 100);
+    benchmark_dfa_alternatives(1000, 100);
+}
+
+/// I think this behaves quadratically, so yay i guess at least better than exponential lol oops
+fn benchmark_dfa_klenee(max: usize, step_size: usize) {
+    let pattern = "a*";
+    let before_parse = Instant::now();
+    let (dfa, dfa_reversed) = build_finding_dfas(pattern);
+    println!("construction: {:?}", before_parse.elapsed());
+
+    let mut times: Vec<Duration> = Vec::new();
+    for i in (1..=(max + 1)).step_by(step_size) {
+        let mut word = String::from("");
+        word.extend("a".repeat(i).chars());
+        // word.push('b');
+        let before_match = Instant::now();
+        let found_match = dfa.find(&word, &dfa_reversed);
+        times.push(before_match.elapsed());
+        assert_eq!(found_match, Some((0, i - 1)));
+        println!("{}: {:?}", i, times.last().unwrap())
+    }
+    export_benchmark_to_csv("dfa_klenee.csv", "number_of_characters", times, step_size);
+}
+
+/// The powerset construction for a pattern of many alternatives, e.g. a list of keywords
+fn benchmark_dfa_alternatives(max: usize, step_size: usize) {
+    let mut times: Vec<Duration> = Vec::new();
+    for i in (1..=(max + 1)).step_by(step_size) {
+        // distinct words of a and b, as there are no digits in a pattern
+        let pattern: Vec<String> = (0..i)
+            .map(|n| format!("c{:b}", n).replace('0', "a").replace('1', "b"))
+            .collect();
+        let nfa = parse(&pattern.join("|"));
+        let before_construction = Instant::now();
+        let dfa = Dfa::from(&nfa);
+        times.push(before_construction.elapsed());
+        assert!(dfa.accept("ca"));
+        println!("{}: {:?}", i, times.last().unwrap())
+    }
+    export_benchmark_to_csv("dfa_alternatives.csv", "number_of_alternatives", times, step_size);
+}
+
+
+fn export_benchmark_to_csv(filename: &str, column: &str, times: Vec<Duration>, step_size: usize) {
+    let full_filename = format!("benches/results/{}", filename);
+    let file = File::create(&full_filename).expect("Unable to create file");
+    println!("Dumped to: {}", full_filename);
+    let mut writer = BufWriter::new(file);
+    writeln!(writer, "{},duration_seconds", column).unwrap();
+    for (i, duration) in times.iter().enumerate() {
+        let size = i * step_size + 1;
+        let secs = duration.as_secs_f64();
+        writeln!(writer, "{},{}", size, secs).unwrap();
+    }
+}
+
+fn build_finding_dfas(pattern: &str) -> (Dfa, Dfa) {
+    let mut nfa = parse(&pattern);
+    let dfa_reversed = Dfa::from(&nfa.reversed());
+    nfa.to_finding();
+    let dfa = Dfa::from(&nfa);
+    (dfa, dfa_reversed)
+}
+
+
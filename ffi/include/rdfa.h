@@ -0,0 +1,72 @@
+/* Generated by cbindgen from src/lib.rs, do not edit. */
+
+#ifndef RDFA_H
+#define RDFA_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * A compiled pattern, only ever handled through a pointer
+ */
+typedef struct Rdfa Rdfa;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Compile the pattern `pattern[0..len)`.
+ * Returns NULL if the pattern is invalid, see `rdfa_last_error` for why. Free the result with `rdfa_free`.
+ *
+ * # Safety
+ * `pattern` must be valid for reads of `len` bytes.
+ */
+struct Rdfa *rdfa_compile(const char *pattern,
+                          size_t len);
+
+/**
+ * Returns 1 if all of `input[0..len)` is accepted, 0 if not and -1 on error, see `rdfa_last_error`.
+ *
+ * # Safety
+ * `rdfa` must come from `rdfa_compile` and `input` must be valid for reads of `len` bytes.
+ */
+int rdfa_is_match(const struct Rdfa *rdfa, const char *input, size_t len);
+
+/**
+ * Find the first match in `input[0..len)`.
+ * Returns 1 and stores the match as `input[*start..*end)` if there is one, 0 if not and -1 on error, see
+ * `rdfa_last_error`.
+ *
+ * # Safety
+ * `rdfa` must come from `rdfa_compile`, `input` must be valid for reads of `len` bytes and `start` and `end` must be
+ * valid for writes.
+ */
+int rdfa_find(const struct Rdfa *rdfa,
+              const char *input,
+              size_t len,
+              size_t *start,
+              size_t *end);
+
+/**
+ * Free a pattern compiled by `rdfa_compile`. Does nothing for NULL.
+ *
+ * # Safety
+ * `rdfa` must come from `rdfa_compile` and must not be used afterwards.
+ */
+void rdfa_free(struct Rdfa *rdfa);
+
+/**
+ * The error of the last call on this thread as null-terminated string, NULL if it succeeded.
+ * The string stays valid until the next call of any `rdfa_` function on the same thread.
+ */
+const char *rdfa_last_error(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* RDFA_H */
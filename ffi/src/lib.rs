@@ -0,0 +1,151 @@
+//! C API of the engine, compiling patterns into a `Matcher`. The header `include/rdfa.h` is generated from this file
+//! with cbindgen, so the doc comments here end up in it as well. `tests/header.rs` checks that it is up to date.
+//!
+//! Patterns and inputs are passed as pointer and length and have to be UTF-8, they don't need to be null-terminated.
+//! Offsets reported by `rdfa_find` are byte offsets. No function ever unwinds into C, a panic is reported as an error.
+
+use rusty_dfa::matcher::Matcher;
+use std::cell::RefCell;
+use std::ffi::{CString, c_char, c_int};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::ptr;
+
+/// A compiled pattern, only ever handled through a pointer
+pub struct Rdfa {
+    matcher: Matcher,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Run `f`, storing its error (or panic) as the last error of this thread and returning `fallback` in that case
+fn guard<T>(fallback: T, f: impl FnOnce() -> Result<T, String>) -> T {
+    let result = catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|panic| {
+        let reason = panic
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        Err(format!("internal error: {}", reason))
+    });
+    let (value, error) = match result {
+        Ok(value) => (value, None),
+        Err(e) => (fallback, Some(CString::new(e.replace('\0', "")).unwrap())),
+    };
+    LAST_ERROR.with(|last| *last.borrow_mut() = error);
+    value
+}
+
+/// Borrow `len` bytes at `text` as `&str`
+///
+/// # Safety
+/// `text` must be valid for reads of `len` bytes (or null, which is an error)
+unsafe fn text<'a>(text: *const c_char, len: usize, what: &str) -> Result<&'a str, String> {
+    if text.is_null() {
+        return Err(format!("{} is null", what));
+    }
+    let bytes = unsafe { std::slice::from_raw_parts(text as *const u8, len) };
+    std::str::from_utf8(bytes).map_err(|e| format!("{} is not UTF-8: {}", what, e))
+}
+
+/// Borrow the matcher behind `rdfa`
+///
+/// # Safety
+/// `rdfa` must come from `rdfa_compile` and not be freed yet (or null, which is an error)
+unsafe fn matcher<'a>(rdfa: *const Rdfa) -> Result<&'a Matcher, String> {
+    match unsafe { rdfa.as_ref() } {
+        Some(rdfa) => Ok(&rdfa.matcher),
+        None => Err(String::from("rdfa is null")),
+    }
+}
+
+/// Compile the pattern `pattern[0..len)`.
+/// Returns NULL if the pattern is invalid, see `rdfa_last_error` for why. Free the result with `rdfa_free`.
+///
+/// # Safety
+/// `pattern` must be valid for reads of `len` bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rdfa_compile(pattern: *const c_char, len: usize) -> *mut Rdfa {
+    guard(ptr::null_mut(), || {
+        let pattern = unsafe { text(pattern, len, "pattern") }?;
+        let matcher = Matcher::new(pattern).map_err(|e| e.to_string())?;
+        Ok(Box::into_raw(Box::new(Rdfa { matcher })))
+    })
+}
+
+/// Returns 1 if all of `input[0..len)` is accepted, 0 if not and -1 on error, see `rdfa_last_error`.
+///
+/// # Safety
+/// `rdfa` must come from `rdfa_compile` and `input` must be valid for reads of `len` bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rdfa_is_match(
+    rdfa: *const Rdfa,
+    input: *const c_char,
+    len: usize,
+) -> c_int {
+    guard(-1, || {
+        let matcher = unsafe { matcher(rdfa) }?;
+        let input = unsafe { text(input, len, "input") }?;
+        Ok(matcher.is_match(input) as c_int)
+    })
+}
+
+/// Find the first match in `input[0..len)`.
+/// Returns 1 and stores the match as `input[*start..*end)` if there is one, 0 if not and -1 on error, see
+/// `rdfa_last_error`.
+///
+/// # Safety
+/// `rdfa` must come from `rdfa_compile`, `input` must be valid for reads of `len` bytes and `start` and `end` must be
+/// valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rdfa_find(
+    rdfa: *const Rdfa,
+    input: *const c_char,
+    len: usize,
+    start: *mut usize,
+    end: *mut usize,
+) -> c_int {
+    guard(-1, || {
+        let matcher = unsafe { matcher(rdfa) }?;
+        let input = unsafe { text(input, len, "input") }?;
+        if start.is_null() || end.is_null() {
+            return Err(String::from("start or end is null"));
+        }
+        let Some((first, last)) = matcher.find(input) else {
+            return Ok(0);
+        };
+        // char indices with an inclusive end to byte offsets with an exclusive one
+        let offsets: Vec<usize> = input
+            .char_indices()
+            .map(|(at, _)| at)
+            .chain([input.len()])
+            .collect();
+        unsafe {
+            *start = offsets[first];
+            *end = offsets[last + 1];
+        }
+        Ok(1)
+    })
+}
+
+/// Free a pattern compiled by `rdfa_compile`. Does nothing for NULL.
+///
+/// # Safety
+/// `rdfa` must come from `rdfa_compile` and must not be used afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rdfa_free(rdfa: *mut Rdfa) {
+    if !rdfa.is_null() {
+        drop(unsafe { Box::from_raw(rdfa) });
+    }
+}
+
+/// The error of the last call on this thread as null-terminated string, NULL if it succeeded.
+/// The string stays valid until the next call of any `rdfa_` function on the same thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn rdfa_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| match &*last.borrow() {
+        Some(error) => error.as_ptr(),
+        None => ptr::null(),
+    })
+}
@@ -0,0 +1,53 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// `target/<profile>`, where cargo puts `librdfa.a` next to the `deps` directory of this test
+fn target_dir() -> PathBuf {
+    let exe = std::env::current_exe().unwrap();
+    exe.parent().unwrap().parent().unwrap().to_path_buf()
+}
+
+#[test]
+fn test_c_harness() {
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+    // `cargo test --workspace` only builds the rlib of this crate, so make sure the static library is up to date, with
+    // the same profile and target directory as this test
+    let target_dir = target_dir();
+    let profile = match target_dir.file_name().unwrap().to_str().unwrap() {
+        "debug" => "dev",
+        profile => profile,
+    };
+    let status = Command::new(env!("CARGO"))
+        .args(["build", "--lib", "-p", "rusty-dfa-ffi"])
+        .args(["--profile", profile])
+        .arg("--target-dir")
+        .arg(target_dir.parent().unwrap())
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let library = target_dir.join("librdfa.a");
+    let binary = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("harness");
+
+    let cc = std::env::var("CC").unwrap_or(String::from("cc"));
+    let output = Command::new(cc)
+        .args(["-std=c99", "-Wall", "-Wextra", "-pedantic", "-Werror", "-I"])
+        .arg(manifest.join("include"))
+        .arg(manifest.join("tests/harness.c"))
+        .arg(&library)
+        .args(["-lpthread", "-ldl", "-lm", "-o"])
+        .arg(&binary)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = Command::new(&binary).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
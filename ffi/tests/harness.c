@@ -0,0 +1,55 @@
+/* Exercises the C API the way a C program would, exits with the number of failed checks. */
+#include <stdio.h>
+#include <string.h>
+
+#include "rdfa.h"
+
+static int failures = 0;
+
+#define CHECK(condition)                                                    \
+    do {                                                                    \
+        if (!(condition)) {                                                 \
+            fprintf(stderr, "%s:%d: %s\n", __FILE__, __LINE__, #condition); \
+            failures++;                                                     \
+        }                                                                   \
+    } while (0)
+
+static int is_match(const Rdfa *rdfa, const char *input)
+{
+    return rdfa_is_match(rdfa, input, strlen(input));
+}
+
+int main(void)
+{
+    size_t start = 0, end = 0;
+    const char *pattern = "aab|ac";
+    Rdfa *rdfa = rdfa_compile(pattern, strlen(pattern));
+    CHECK(rdfa != NULL);
+    CHECK(rdfa_last_error() == NULL);
+
+    CHECK(is_match(rdfa, "aab") == 1);
+    CHECK(is_match(rdfa, "ac") == 1);
+    CHECK(is_match(rdfa, "aac") == 0);
+    CHECK(rdfa_find(rdfa, "cadaabf", 7, &start, &end) == 1);
+    CHECK(start == 3 && end == 6);
+    CHECK(rdfa_find(rdfa, "cab", 3, &start, &end) == 0);
+    /* byte offsets, the input doesn't need to be null-terminated */
+    CHECK(rdfa_find(rdfa, "\xc3\xa4" "aacXXX", 5, &start, &end) == 1);
+    CHECK(start == 3 && end == 5);
+
+    /* invalid UTF-8 */
+    CHECK(rdfa_is_match(rdfa, "\xff", 1) == -1);
+    CHECK(rdfa_last_error() != NULL && strstr(rdfa_last_error(), "UTF-8") != NULL);
+    /* errors are reset by the next successful call */
+    CHECK(is_match(rdfa, "ac") == 1);
+    CHECK(rdfa_last_error() == NULL);
+    rdfa_free(rdfa);
+
+    CHECK(rdfa_compile("(a|b", 4) == NULL);
+    CHECK(rdfa_last_error() != NULL && strcmp(rdfa_last_error(), "Unclosed '(' at 0..1") == 0);
+    CHECK(rdfa_is_match(NULL, "a", 1) == -1);
+    CHECK(rdfa_find(NULL, "a", 1, &start, &end) == -1);
+    rdfa_free(NULL);
+
+    return failures;
+}
@@ -0,0 +1,40 @@
+use std::path::Path;
+
+/// Generate the header from the `extern "C"` functions in `src/lib.rs`
+fn generate() -> String {
+    let config = cbindgen::Config {
+        language: cbindgen::Language::C,
+        header: Some(String::from(
+            "/* Generated by cbindgen from src/lib.rs, do not edit. */",
+        )),
+        include_guard: Some(String::from("RDFA_H")),
+        usize_is_size_t: true,
+        documentation_style: cbindgen::DocumentationStyle::Doxy,
+        // wrap everything in `extern "C"` when included from C++
+        cpp_compat: true,
+        ..Default::default()
+    };
+    let mut header = Vec::new();
+    cbindgen::Builder::new()
+        .with_crate(env!("CARGO_MANIFEST_DIR"))
+        .with_config(config)
+        .generate()
+        .expect("Unable to generate rdfa.h")
+        .write(&mut header);
+    String::from_utf8(header).unwrap()
+}
+
+/// The header is committed rather than generated on every build, which would write to the source tree
+#[test]
+fn test_header_is_up_to_date() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("include/rdfa.h");
+    let header = generate();
+    if std::env::var_os("RDFA_UPDATE_HEADER").is_some() {
+        std::fs::write(&path, header).unwrap();
+        return;
+    }
+    assert!(
+        std::fs::read_to_string(&path).unwrap() == header,
+        "include/rdfa.h is out of date, regenerate it with `RDFA_UPDATE_HEADER=1 cargo test -p rusty-dfa-ffi --test header`"
+    );
+}
@@ -0,0 +1,47 @@
+use proc_macro::TokenStream;
+use rusty_dfa::automata::Dfa;
+use rusty_dfa::parse::try_parse;
+use syn::{LitStr, parse_macro_input};
+
+/// Compile a pattern into a `StaticDfa` while compiling the crate using it, so neither parsing nor the powerset
+/// construction happen at runtime. Invalid patterns are compile errors pointing at the pattern.
+///
+/// The expansion is a constant expression, so it can initialize a `static`:
+/// ```
+/// use rusty_dfa::automata::static_dfa::StaticDfa;
+/// use rusty_dfa_macros::dfa;
+///
+/// static BINARY: StaticDfa = dfa!("(0|1)+");
+/// assert!(BINARY.is_match("0110"));
+/// assert_eq!(BINARY.find("x101y"), Some((1, 3)));
+/// ```
+///
+/// ```compile_fail
+/// let unclosed = rusty_dfa_macros::dfa!("(a|b");
+/// ```
+#[proc_macro]
+pub fn dfa(input: TokenStream) -> TokenStream {
+    let literal = parse_macro_input!(input as LitStr);
+    let pattern = literal.value();
+    let nfa = match try_parse(&pattern) {
+        Ok(nfa) => nfa,
+        Err(e) => {
+            // a subspan only lines up with the source if the literal is written without escapes
+            let token = literal.token();
+            let span = if token.to_string() == format!("{:?}", pattern) {
+                token
+                    .subspan(e.span.start + 1..e.span.end + 1)
+                    .unwrap_or(literal.span())
+            } else {
+                literal.span()
+            };
+            return syn::Error::new(span, format!("invalid pattern: {}", e))
+                .to_compile_error()
+                .into();
+        }
+    };
+    let dfa = Dfa::from(&nfa);
+    dfa.to_rust_static()
+        .parse()
+        .expect("generated code is always valid")
+}
@@ -0,0 +1,42 @@
+use rusty_dfa::automata::Automaton;
+use rusty_dfa::automata::static_dfa::StaticDfa;
+use rusty_dfa_macros::dfa;
+
+static GREETING: StaticDfa = dfa!("(hello|hi)+ world");
+
+#[test]
+fn test_static() {
+    assert!(GREETING.is_match("hello world"));
+    assert!(GREETING.is_match("hihello world"));
+    assert!(!GREETING.is_match("hello"));
+    assert_eq!(GREETING.find("oh, hi world!"), Some((4, 11)));
+    assert_eq!(GREETING.find("hey world"), None);
+}
+
+#[test]
+fn test_agrees_with_runtime_construction() {
+    let compiled = dfa!("a?b+(a|c)?|c+");
+    let dfa = rusty_dfa::automata::Dfa::from(&rusty_dfa::parse::parse("a?b+(a|c)?|c+"));
+    for word in dfa.words(Some(6)) {
+        assert!(compiled.is_match(&word), "{}", word);
+    }
+    for word in ["", "a", "ac", "bcc", "cb", "abab"] {
+        assert_eq!(compiled.accept(word), dfa.accept(word), "{}", word);
+    }
+}
+
+#[test]
+fn test_boolean_operators() {
+    // contains an a, but doesn't end with it
+    let compiled = dfa!("(a|b)*a(a|b)*&~((a|b)*a)");
+    assert!(compiled.is_match("ab"));
+    assert!(!compiled.is_match("ba"));
+    assert!(!compiled.is_match("bb"));
+}
+
+#[test]
+fn test_unicode() {
+    let compiled = dfa!("(ä|ö)+ü");
+    assert!(compiled.is_match("äöü"));
+    assert_eq!(compiled.find("xöü"), Some((1, 2)));
+}
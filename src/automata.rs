@@ -5,8 +5,21 @@ use std::any::type_name;
 use std::cell::RefCell;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::fmt::{Debug, Display, Formatter, Write};
+use std::ops::Range;
 use std::thread::current;
 
+pub mod ambiguity;
+pub mod binary;
+mod boolean;
+mod counting;
+mod enumerate;
+mod minimize;
+mod queries;
+mod sampling;
+pub mod static_dfa;
+pub mod trace;
+mod witness;
+
 pub trait Automaton {
     /// Validate the `Automaton`
     /// returns: Ok(()) if valid, Err(reason) if not
@@ -30,6 +43,8 @@ pub struct Nfa {
     pub q_start: usize,
     pub q_accepting: HashSet<usize>,
     pub alphabet: HashSet<Symbol>,
+    /// The byte range of the pattern each state was created for, if the `Nfa` was built by `parse`
+    pub spans: HashMap<usize, Range<usize>>,
 }
 
 impl Nfa {
@@ -39,19 +54,7 @@ impl Nfa {
         q_start: usize,
         q_accepting: HashSet<usize>,
     ) -> Nfa {
-        let alphabet = transitions
-            .iter()
-            .map(|(_, w, _)| w)
-            .filter(|w| **w != Symbol::EPSILON)
-            .cloned()
-            .collect();
-        let nfa = Nfa {
-            states,
-            transitions,
-            q_start,
-            q_accepting,
-            alphabet,
-        };
+        let nfa = Nfa::_new(states, transitions, q_start, q_accepting);
         match nfa.validate() {
             Ok(_) => {}
             Err(e) => {
@@ -65,6 +68,40 @@ impl Nfa {
         nfa
     }
 
+    /// Same as `Nfa::new`, but returns the reason instead of panicking if the resulting `Nfa` would be invalid.
+    pub fn try_new(
+        states: Vec<usize>,
+        transitions: HashSet<(usize, Symbol, usize)>,
+        q_start: usize,
+        q_accepting: HashSet<usize>,
+    ) -> Result<Nfa, String> {
+        let nfa = Nfa::_new(states, transitions, q_start, q_accepting);
+        nfa.validate()?;
+        Ok(nfa)
+    }
+
+    fn _new(
+        states: Vec<usize>,
+        transitions: HashSet<(usize, Symbol, usize)>,
+        q_start: usize,
+        q_accepting: HashSet<usize>,
+    ) -> Nfa {
+        let alphabet = transitions
+            .iter()
+            .map(|(_, w, _)| w)
+            .filter(|w| **w != Symbol::EPSILON)
+            .cloned()
+            .collect();
+        Nfa {
+            states,
+            transitions,
+            q_start,
+            q_accepting,
+            alphabet,
+            spans: HashMap::new(),
+        }
+    }
+
     /// Construct a fully valid `Automaton` accepting exactly the passed `Symbol`.
     pub fn from_symbol(s: &Symbol) -> Self {
         match s {
@@ -92,6 +129,22 @@ impl Nfa {
         }
     }
 
+    /// Construct an `Nfa` accepting exactly the language of `dfa`.
+    /// Every DFA is an NFA as well, so this simply copies states and transitions over.
+    pub fn from_dfa(dfa: &Dfa) -> Self {
+        let transitions = dfa
+            .transitions
+            .iter()
+            .map(|((from, with), to)| (*from, *with, *to))
+            .collect();
+        Nfa::new(
+            dfa.states.clone(),
+            transitions,
+            dfa.q_start,
+            dfa.q_accepting.clone(),
+        )
+    }
+
     // fixme: non-determinism due to how iter() on hashsets works but okay for prototyping
     // in order to get greedy (but deterministic) behaviour, I should probably move all hashsets to vectors
     fn find_transitions(&self, from: usize, c: Symbol) -> Vec<&(usize, Symbol, usize)> {
@@ -187,6 +240,7 @@ impl Nfa {
         self.states.extend(&other.states);
         self.transitions.extend(other.transitions);
         self.alphabet.extend(other.alphabet);
+        self.spans.extend(other.spans);
         for f in self.q_accepting.iter() {
             self.transitions
                 .insert((*f, Symbol::EPSILON, other.q_start));
@@ -198,6 +252,7 @@ impl Nfa {
         self.states.extend(&other.states);
         self.transitions.extend(other.transitions);
         self.alphabet.extend(other.alphabet);
+        self.spans.extend(other.spans);
 
         let union_state = next_state();
         self.states.push(union_state);
@@ -230,6 +285,7 @@ impl Nfa {
             self.q_start,
             HashSet::from([self.q_start]),
         );
+        reversed.spans = self.spans.clone();
 
         for (from, with, to) in &self.transitions {
             reversed.transitions.insert((*to, with.clone(), *from));
@@ -267,25 +323,40 @@ impl Nfa {
         }
     }
 
-    // todo: replace this with sneaky workaround using Symbol::EVERYTHING in find_transitions
-    fn find_symbol_transitions(&self, from: &usize) -> Vec<&(usize, Symbol, usize)> {
-        self.transitions
-            .iter()
-            .filter(|t| &t.0 == from && t.1 != Symbol::EPSILON)
-            .collect()
-    }
-
-    /// Calculate all possible successor states for a single state
-    fn successors_single(&self) -> HashMap<(usize, Symbol), BTreeSet<usize>> {
-        let mut successors: HashMap<(usize, Symbol), BTreeSet<usize>> = HashMap::new();
+    /// Calculate all possible successor states for a single state, by state and symbol
+    fn successors_single(&self) -> HashMap<usize, HashMap<Symbol, BTreeSet<usize>>> {
+        // index the epsilon transitions once, instead of searching all transitions for every state of every closure
+        let mut epsilon: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (from, with, to) in &self.transitions {
+            if *with == Symbol::EPSILON {
+                epsilon.entry(*from).or_default().push(*to);
+            }
+        }
 
-        for state in &self.states {
-            for transition in self.find_symbol_transitions(state) {
-                successors
-                    .entry((*state, transition.1))
-                    .or_default()
-                    .extend(self.ec(transition.2)); // behaves like a union here
+        let mut closures: HashMap<usize, BTreeSet<usize>> = HashMap::new();
+        let mut successors: HashMap<usize, HashMap<Symbol, BTreeSet<usize>>> = HashMap::new();
+        for (from, with, to) in &self.transitions {
+            if *with == Symbol::EPSILON {
+                continue;
             }
+            let closure = closures.entry(*to).or_insert_with(|| {
+                let mut closure = BTreeSet::from([*to]);
+                let mut stack = vec![*to];
+                while let Some(state) = stack.pop() {
+                    for next in epsilon.get(&state).into_iter().flatten() {
+                        if closure.insert(*next) {
+                            stack.push(*next);
+                        }
+                    }
+                }
+                closure
+            });
+            successors
+                .entry(*from)
+                .or_default()
+                .entry(*with)
+                .or_default()
+                .extend(closure.iter().cloned()); // behaves like a union here
         }
 
         successors
@@ -295,20 +366,15 @@ impl Nfa {
     fn successors_multiple(
         &self,
         states: &BTreeSet<usize>,
-        successors_single: &HashMap<(usize, Symbol), BTreeSet<usize>>,
+        successors_single: &HashMap<usize, HashMap<Symbol, BTreeSet<usize>>>,
     ) -> HashMap<Symbol, BTreeSet<usize>> {
         let mut successors_by_symbol: HashMap<Symbol, BTreeSet<usize>> = HashMap::new();
         for state in states {
-            let alphabet: HashSet<Symbol> = self
-                .find_symbol_transitions(state)
-                .iter()
-                .map(|t| t.1)
-                .collect();
-            for s in alphabet.into_iter() {
+            for (s, targets) in successors_single.get(state).into_iter().flatten() {
                 successors_by_symbol
-                    .entry(s)
+                    .entry(*s)
                     .or_default()
-                    .extend(successors_single.get(&(*state, s)).unwrap());
+                    .extend(targets.iter().cloned());
             }
         }
         successors_by_symbol
@@ -387,6 +453,10 @@ pub struct Dfa {
     transitions: HashMap<(usize, Symbol), usize>,
     pub q_start: usize,
     q_accepting: HashSet<usize>,
+    // needed to totalize the automaton, e.g. when complementing it
+    alphabet: HashSet<Symbol>,
+    // the set of `Nfa` states each state stands for, see `nfa_states`
+    nfa_states: HashMap<usize, BTreeSet<usize>>,
 }
 
 impl Dfa {
@@ -396,16 +466,61 @@ impl Dfa {
         q_start: usize,
         q_accepting: HashSet<usize>,
     ) -> Dfa {
+        let alphabet = transitions.keys().map(|(_, w)| *w).collect();
         Dfa {
             states,
             transitions,
             q_start,
             q_accepting,
+            alphabet,
+            nfa_states: HashMap::new(),
         }
     }
 
+    // read-only access, a `Dfa` must not be modified after construction
+
+    pub fn states(&self) -> &Vec<usize> {
+        &self.states
+    }
+
+    pub fn transitions(&self) -> &HashMap<(usize, Symbol), usize> {
+        &self.transitions
+    }
+
+    pub fn q_accepting(&self) -> &HashSet<usize> {
+        &self.q_accepting
+    }
+
+    pub fn alphabet(&self) -> &HashSet<Symbol> {
+        &self.alphabet
+    }
+
+    /// The set of `Nfa` states each state stands for, as far as known. `Dfa::from_with_provenance` records them for
+    /// every state, `totalize` and `minimize` carry them over, other constructions start without any.
+    pub fn nfa_states(&self) -> &HashMap<usize, BTreeSet<usize>> {
+        &self.nfa_states
+    }
+
+    /// Drop the sets of `nfa_states`, e.g. to save memory when they aren't needed anymore.
+    pub fn without_nfa_states(mut self) -> Dfa {
+        self.nfa_states = HashMap::new();
+        self
+    }
+
     /// Powerset Construction of a DFA from the passed `Nfa`.
     pub fn from(nfa: &Nfa) -> Dfa {
+        Dfa::powerset(nfa).0
+    }
+
+    /// Powerset Construction of a DFA from the passed `Nfa`, which keeps the set of `Nfa` states every state stands
+    /// for as `nfa_states`.
+    pub fn from_with_provenance(nfa: &Nfa) -> Dfa {
+        let (mut dfa, id_to_state_set) = Dfa::powerset(nfa);
+        dfa.nfa_states = id_to_state_set.into_iter().collect();
+        dfa
+    }
+
+    fn powerset(nfa: &Nfa) -> (Dfa, BiMap<usize, BTreeSet<usize>>) {
         let successors = nfa.successors_single();
         let new_q0: BTreeSet<usize> = nfa.ec(nfa.q_start).drain(..).collect();
         let new_q0_id = next_state();
@@ -414,6 +529,7 @@ impl Dfa {
         id_to_state_set.insert(new_q0_id.clone(), new_q0.clone());
 
         let mut dfa = Dfa::new(vec![new_q0_id], HashMap::new(), new_q0_id, HashSet::new());
+        dfa.alphabet = nfa.alphabet.clone();
         if nfa.contains_accepting_state(&new_q0) {
             dfa.q_accepting.insert(new_q0_id);
         }
@@ -442,120 +558,18 @@ impl Dfa {
             }
             i += 1
         }
+        // `nfa.alphabet` is not kept up to date when transitions are inserted by hand, e.g. in `Nfa::reversed`
+        dfa.alphabet.extend(dfa.transitions.keys().map(|(_, w)| *w));
 
-        dfa
-    }
-
-    /// Find all characters at which the Dfa is in an accepting state
-    fn _find_ends(&self, input: &str, allow_resets: bool) -> Vec<usize> {
-        let mut current = self.q_start;
-        let mut ends: Vec<usize> = Vec::new();
-        for (pos, c) in input.chars().enumerate() {
-            if let Some(next) = self.transitions.get(&(current, CHAR(c))) {
-                current = *next;
-                if self.q_accepting.contains(&current) {
-                    ends.push(pos);
-                }
-                continue;
-            }
-            if !allow_resets {
-                break;
-            }
-            current = self.q_start;
-        }
-        ends
+        (dfa, id_to_state_set)
     }
 
     /// Find all matches of the pattern represented by `self` in `input`.
     /// Returns an ordered vector of tuples `(start, end)`, where each tuple represents an individual match.
-    /// Why am I using an ASM for this
+    /// Matches are leftmost longest, don't overlap and are never empty.
     /// note: `input` is in actuality reverse of the word we're searching, while `reversed` is the dfa describing the pattern in the correct orientation
     pub fn find_all(&self, input: &str, reversed: &Dfa) -> Option<Vec<(usize, usize)>> {
-        // print!("{:?}", self);
-        // println!("{:?}", reversed);
-        let ends = self._find_ends(input, true);
-        if ends.is_empty() {
-            return None;
-        }
-        // We need to ensure we start the reversed match on the exact character of the respective end
-        let cutoff = input.len() - ends.last().unwrap() - 1;
-        let ends_in_reverse: Vec<usize> = ends
-            .iter()
-            .map(|pos| input.len() - *pos - 1 - cutoff)
-            .collect();
-        let mut ends_in_reverse = ends_in_reverse.iter().rev().peekable();
-
-        // variables for matching automaton
-        let mut current = reversed.q_start;
-        let mut step_bro_im_stuck = false;
-
-        // variables for greediness automaton
-        enum State {
-            END,
-            START,
-        }
-        let mut state = State::END;
-        let mut current_end: Option<usize> = ends_in_reverse.peek().cloned().cloned();
-        let mut pairs: Vec<(usize, usize)> = Vec::new();
-        let mut best_start: Option<usize> = None;
-        // weird ahh double state machine
-        // while simulating a run of the reversed input on `reversed`, we also, constantly,
-        // run a second state machine (with side-effects) that tells us whether we need a
-        // starting position to create a match, or an end position to start a new one.
-        for (pos, c) in input.chars().rev().skip(cutoff).enumerate() {
-            let is_end =
-                { ends_in_reverse.peek() == Some(&&pos) && ends_in_reverse.next() == Some(&pos) };
-            if let Some(next) = reversed.transitions.get(&(current, CHAR(c))) {
-                current = *next;
-            } else {
-                step_bro_im_stuck = true;
-            }
-
-            let transition_condition = (&state, reversed.q_accepting.contains(&current), is_end);
-            match transition_condition {
-                (_, false, false) => {}
-                (State::END, false, true) => {
-                    if current_end.is_none() {
-                        current_end = Some(pos)
-
-                    }
-                }
-                (State::END, true, _) => {
-                    state = State::START;
-                    best_start = Some(pos)
-                }
-                (State::START, true, false) => {
-                    if !step_bro_im_stuck {
-                        best_start = Some(pos)
-                    }
-                }
-                (State::START, true, true) => {
-                    if !step_bro_im_stuck {
-                        best_start = Some(pos);
-                        continue;
-                    }
-                    pairs.push((current_end.unwrap() + cutoff, best_start.unwrap() + cutoff));
-                    current_end = Some(pos);
-                    current = reversed.q_start;
-                    state = State::END;
-                }
-                // only reset a match if the character is not a start, but an end => greediness (maybe)
-                (State::START, false, true) => {
-                    pairs.push((current_end.unwrap() + cutoff, best_start.unwrap() + cutoff));
-                    current_end = Some(pos);
-                    current = reversed.q_start;
-                    state = State::END;
-                }
-            }
-        }
-        // pairs.push((current_end?, best_start?));
-        if let Some(end) = current_end
-            && let Some(start) = best_start
-        {
-            pairs.push((end + cutoff, start + cutoff));
-        }
-
-        Some(pairs)
+        find_all(self, input, reversed)
     }
 
     /// Find the first match of the pattern represented by `self` in `input`.
@@ -566,11 +580,6 @@ impl Dfa {
             None
         }
     }
-
-    // may not actually be needed we'll see
-    pub fn minimize(&self) {
-        todo!()
-    }
 }
 
 impl Automaton for Dfa {
@@ -591,6 +600,9 @@ impl Automaton for Dfa {
                     transition
                 ));
             }
+            if !self.alphabet.contains(&transition.0.1) {
+                return Err(format!("{:?} uses a symbol ∉ E", transition));
+            }
         }
 
         let mut num_state: HashSet<usize> = HashSet::new();
@@ -633,10 +645,178 @@ impl Debug for Dfa {
         writeln!(f, "\t}}")?;
         writeln!(f, "\tq_0: {:?},", self.q_start)?;
         writeln!(f, "\tF: {:?},", self.q_accepting)?;
+        writeln!(f, "\tE: {:?}", self.alphabet)?;
         write!(f, "}}")
     }
 }
 
+////////////////////////////////////////////////////// MATCHING ///////////////////////////////////////////////////////
+
+/// Stepwise access to a deterministic automaton, so the matching algorithms can run on both an owned `Dfa`
+/// and a `DfaRef` reading straight from a serialized one.
+pub trait Deterministic {
+    fn start(&self) -> usize;
+
+    /// The state reached from `state` with `c`, `None` if the run dies
+    fn step(&self, state: usize, c: char) -> Option<usize>;
+
+    fn is_accepting(&self, state: usize) -> bool;
+}
+
+impl Deterministic for Dfa {
+    fn start(&self) -> usize {
+        self.q_start
+    }
+
+    fn step(&self, state: usize, c: char) -> Option<usize> {
+        self.transitions.get(&(state, CHAR(c))).cloned()
+    }
+
+    fn is_accepting(&self, state: usize) -> bool {
+        self.q_accepting.contains(&state)
+    }
+}
+
+/// Find all characters at which `dfa` is in an accepting state
+fn _find_ends(dfa: &impl Deterministic, input: &str, allow_resets: bool) -> Vec<usize> {
+    let mut current = dfa.start();
+    let mut ends: Vec<usize> = Vec::new();
+    for (pos, c) in input.chars().enumerate() {
+        if let Some(next) = dfa.step(current, c) {
+            current = next;
+            if dfa.is_accepting(current) {
+                ends.push(pos);
+            }
+            continue;
+        }
+        if !allow_resets {
+            break;
+        }
+        current = dfa.start();
+    }
+    ends
+}
+
+/// A position a match starts at while running `find_all`
+struct Candidate {
+    start: usize,
+    /// The state of its run, `None` once the run died or was taken over by an earlier one
+    state: Option<usize>,
+    /// The end of the longest match from `start` so far
+    end: Option<usize>,
+}
+
+/// Find all matches of the pattern represented by `reversed` in the word whose reverse is `input`.
+/// Returns an ordered vector of inclusive `(start, end)` char indices, where each tuple represents an individual match.
+/// Matches are leftmost longest and don't overlap. Empty matches are never reported, as there is no `(start, end)`
+/// for them.
+/// note: `input` is in actuality reverse of the word we're searching and `dfa` the finding dfa of the reversed pattern,
+/// while `reversed` is the dfa describing the pattern in the correct orientation
+///
+/// Running `dfa` over `input` yields every position a match starts at. Running `reversed` from each of these until it
+/// gets stuck would read the same characters over and over again, e.g. for `(a|b)*c|a` on a line of `a`s. Instead,
+/// a single pass runs all of them in lockstep: Two runs in the same state accept at the same positions from then on,
+/// and a further match of the earlier one would overlap the later one anyway, so only the earlier one goes on.
+/// There are never more runs at once than `reversed` has states, so both passes are linear in the length of `input`.
+pub(crate) fn find_all<D: Deterministic>(
+    dfa: &D,
+    input: &str,
+    reversed: &D,
+) -> Option<Vec<(usize, usize)>> {
+    let word: Vec<char> = input.chars().rev().collect();
+    // a match of the reversed pattern ending at `pos` of the reversed input is a match starting at `len - pos - 1`
+    let mut starts = vec![false; word.len()];
+    for pos in _find_ends(dfa, input, true) {
+        starts[word.len() - pos - 1] = true;
+    }
+
+    // Every candidate starts after the end of the one before, as a match drops all candidates started after it.
+    // Those before `first` have already been reported.
+    let mut candidates: Vec<Candidate> = Vec::new();
+    let mut first = 0;
+    // indices of the candidates whose runs go on, in order
+    let mut running: Vec<usize> = Vec::new();
+    let mut states: HashSet<usize> = HashSet::new();
+    let mut pairs: Vec<(usize, usize)> = Vec::new();
+    for (pos, c) in word.iter().enumerate() {
+        if starts[pos] {
+            running.push(candidates.len());
+            candidates.push(Candidate {
+                start: pos,
+                state: Some(reversed.start()),
+                end: None,
+            });
+        }
+
+        states.clear();
+        let mut still_running = Vec::new();
+        for i in running {
+            // dropped by a match of an earlier candidate in this very step
+            let Some(candidate) = candidates.get_mut(i) else {
+                break;
+            };
+            candidate.state = candidate.state.and_then(|q| reversed.step(q, *c));
+            let Some(state) = candidate.state else {
+                continue;
+            };
+            if !states.insert(state) {
+                candidate.state = None;
+                continue;
+            }
+            still_running.push(i);
+            if reversed.is_accepting(state) {
+                candidate.end = Some(pos);
+                candidates.truncate(i + 1);
+            }
+        }
+        running = still_running;
+
+        while let Some(candidate) = candidates.get(first)
+            && candidate.state.is_none()
+        {
+            if let Some(end) = candidate.end {
+                pairs.push((candidate.start, end));
+            }
+            first += 1;
+        }
+    }
+    pairs.extend(
+        candidates[first..]
+            .iter()
+            .filter_map(|candidate| Some((candidate.start, candidate.end?))),
+    );
+
+    if pairs.is_empty() { None } else { Some(pairs) }
+}
+
+/// Find the leftmost longest non-empty match of `dfa` in `input` by trying every start, just like `Nfa::find`.
+/// Unlike `find_all`, this gets by without a reversed automaton, at the cost of being quadratic in the worst case.
+pub(crate) fn find_leftmost_longest(
+    dfa: &impl Deterministic,
+    input: &str,
+) -> Option<(usize, usize)> {
+    let mut rest = input.chars();
+    let mut start = 0;
+    loop {
+        let mut current = dfa.start();
+        let mut end = None;
+        for (pos, c) in rest.clone().enumerate() {
+            match dfa.step(current, c) {
+                Some(next) => current = next,
+                None => break,
+            }
+            if dfa.is_accepting(current) {
+                end = Some(start + pos);
+            }
+        }
+        if let Some(end) = end {
+            return Some((start, end));
+        }
+        rest.next()?;
+        start += 1;
+    }
+}
+
 ///////////////////////////////////////////////////// HELPER TYPES ////////////////////////////////////////////////////
 
 // I don't plan on threading this (yet) so for now it's fine
@@ -644,7 +824,7 @@ thread_local! {
     static STATE_GEN: RefCell<usize> = RefCell::new(0);
 }
 
-fn next_state() -> usize {
+pub(crate) fn next_state() -> usize {
     STATE_GEN.with(|g| {
         let mut cell = g.borrow_mut();
         let next = *cell;
@@ -0,0 +1,491 @@
+use crate::automata::{Dfa, Nfa, Symbol};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+
+////////////////////////////////////////////////////// AMBIGUITY //////////////////////////////////////////////////////
+// A DFA doesn't care in how many ways an `Nfa` can accept a word, but a backtracking engine does: it tries them one
+// after the other, and if there are exponentially many ways to fail on a word, it takes exponentially long to reject
+// it (ReDoS). Following Weideman et al., "Analyzing Matching Time Behavior of Backtracking Regular Expression
+// Matchers by Using Ambiguity of NFA", the `Nfa` is
+// - exponentially ambiguous (EDA) if there is a state `q` and a word `w` with two different paths from `q` back to
+//   `q` reading `w`. Then `w^n` can be read in `2^n` ways.
+// - polynomially ambiguous (IDA) if there are states `p != q` and a word `w` with paths from `p` to `p`, from `p` to
+//   `q` and from `q` to `q`, all reading `w`. Then `w^n` can be read in `n` ways, and a chain of `d` of these, each
+//   reachable from the one before, gives `n^d` ways.
+// Both are found with product automata: Two different paths reading the same word are a path in the product of
+// the `Nfa` with itself, and three paths one in the product with itself twice.
+//
+// A backtracking engine follows epsilon transitions just like any other, so two different ways through epsilon
+// transitions are two different paths as well, e.g. in `(a+)+`. Before building any product, epsilon transitions are
+// eliminated while keeping count of these ways. Every way through epsilon transitions that doesn't take the same
+// epsilon transition twice counts, as that's as far as an engine goes before it detects an empty loop.
+
+/// How many ways there are for an `Nfa` to read a word, see `Nfa::ambiguity`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Ambiguity {
+    /// Any word is read in at most a constant number of ways, which is harmless
+    Bounded,
+    /// Some words of length `n` are read in about `n^degree` ways
+    Polynomial { degree: usize, attack: Attack },
+    /// Some words of length `n` are read in about `2^n` ways
+    Exponential { attack: Attack },
+}
+
+/// A family of words making a backtracking engine try many ways to read them, see `Attack::word`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Attack {
+    /// Leads to where the ambiguity is
+    pub prefix: String,
+    /// The words to repeat, each followed by a word leading to the next one to repeat
+    pub pumps: Vec<(String, String)>,
+    /// Makes sure the word is rejected, so the engine has to try every way before giving up
+    pub suffix: String,
+}
+
+impl Attack {
+    /// The word of the attack repeating every pump `n` times, which is rejected for any `n`
+    pub fn word(&self, n: usize) -> String {
+        let mut word = self.prefix.clone();
+        for (pump, then) in &self.pumps {
+            word.push_str(&pump.repeat(n));
+            word.push_str(then);
+        }
+        word.push_str(&self.suffix);
+        word
+    }
+}
+
+impl Display for Attack {
+    /// Write the attack like `"a" + n * "ab" + "!"`
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut parts: Vec<String> = Vec::new();
+        let quoted = |word: &str| (!word.is_empty()).then(|| format!("{:?}", word));
+        parts.extend(quoted(&self.prefix));
+        for (pump, then) in &self.pumps {
+            parts.push(format!("n * {:?}", pump));
+            parts.extend(quoted(then));
+        }
+        parts.extend(quoted(&self.suffix));
+        write!(f, "{}", parts.join(" + "))
+    }
+}
+
+impl Nfa {
+    /// Analyze how many ways there are for `self` to read a word, i.e. how long a backtracking engine using `self` may
+    /// take for it. An exponential ambiguity is reported over a polynomial one, and polynomial ambiguity with the
+    /// highest degree found.
+    ///
+    /// Note that `&` and `~` are compiled through a `Dfa` by `parse`, so their parts are never ambiguous. Backtracking
+    /// engines don't support them anyway.
+    pub fn ambiguity(&self) -> Ambiguity {
+        let graph = Graph::from(self).trimmed();
+        if let Some(attack) = graph.exponential() {
+            return Ambiguity::Exponential {
+                attack: self.finish(attack),
+            };
+        }
+        match graph.polynomial() {
+            Some((degree, attack)) => Ambiguity::Polynomial {
+                degree,
+                attack: self.finish(attack),
+            },
+            None => Ambiguity::Bounded,
+        }
+    }
+
+    /// Find a suffix for `attack` so that `attack.word(n)` is rejected for every `n`
+    fn finish(&self, mut attack: Attack) -> Attack {
+        let dfa = Dfa::from(self);
+        let step = |states: &BTreeSet<Option<usize>>, word: &str| -> BTreeSet<Option<usize>> {
+            states
+                .iter()
+                .map(|state| dfa.run_from(*state, word))
+                .collect()
+        };
+
+        // every state the `Dfa` can be in after prefix and pumps, no matter how often they are repeated
+        let mut states: BTreeSet<Option<usize>> =
+            step(&BTreeSet::from([Some(dfa.q_start)]), &attack.prefix);
+        for (pump, then) in &attack.pumps {
+            loop {
+                let more: BTreeSet<Option<usize>> =
+                    states.union(&step(&states, pump)).cloned().collect();
+                if more == states {
+                    break;
+                }
+                states = more;
+            }
+            states = step(&states, then);
+        }
+
+        let mut alphabet: Vec<char> = self
+            .alphabet
+            .iter()
+            .filter_map(|s| match s {
+                Symbol::CHAR(c) => Some(*c),
+                _ => None,
+            })
+            .collect();
+        alphabet.sort();
+        let rejected = |states: &BTreeSet<Option<usize>>| {
+            states
+                .iter()
+                .all(|state| state.is_none_or(|q| !dfa.q_accepting.contains(&q)))
+        };
+        let successors = |states: &BTreeSet<Option<usize>>| {
+            alphabet
+                .iter()
+                .map(|c| (*c, step(states, &c.to_string())))
+                .collect()
+        };
+        // if every continuation is accepted by some of these states, a symbol not occurring in the pattern does it
+        attack.suffix = shortest_word(states, successors, rejected, false)
+            .unwrap_or_else(|| ('!'..).find(|c| !alphabet.contains(c)).unwrap().to_string());
+        attack
+    }
+}
+
+impl Dfa {
+    /// The state `self` ends up in after reading `word` from `state`, `None` if the run dies
+    fn run_from(&self, state: Option<usize>, word: &str) -> Option<usize> {
+        word.chars().try_fold(state?, |q, c| {
+            self.transitions.get(&(q, Symbol::CHAR(c))).cloned()
+        })
+    }
+}
+
+/// An `Nfa` without epsilon transitions, where every transition knows in how many ways it can be taken
+struct Graph {
+    start: usize,
+    states: BTreeSet<usize>,
+    accepting: HashSet<usize>,
+    /// `(from, symbol, to) -> ways`, where the number of ways is capped at 2 as it's only ever compared against 1
+    transitions: BTreeMap<(usize, char, usize), usize>,
+}
+
+impl From<&Nfa> for Graph {
+    fn from(nfa: &Nfa) -> Graph {
+        let mut epsilon: HashMap<usize, Vec<usize>> = HashMap::new();
+        let mut reading: HashMap<usize, Vec<(char, usize)>> = HashMap::new();
+        for (from, with, to) in &nfa.transitions {
+            match with {
+                Symbol::CHAR(c) => reading.entry(*from).or_default().push((*c, *to)),
+                Symbol::EPSILON => epsilon.entry(*from).or_default().push(*to),
+                Symbol::EMPTY => {}
+            }
+        }
+
+        // only the start and states entered by reading a symbol remain
+        let mut states: BTreeSet<usize> = reading.values().flatten().map(|(_, to)| *to).collect();
+        states.insert(nfa.q_start);
+
+        let mut accepting: HashSet<usize> = HashSet::new();
+        let mut transitions: BTreeMap<(usize, char, usize), usize> = BTreeMap::new();
+        for state in &states {
+            let mut ways: HashMap<usize, usize> = HashMap::new();
+            epsilon_ways(*state, &epsilon, &mut HashSet::new(), &mut ways);
+            for (via, n) in ways {
+                if nfa.q_accepting.contains(&via) {
+                    accepting.insert(*state);
+                }
+                for (c, to) in reading.get(&via).into_iter().flatten() {
+                    let count = transitions.entry((*state, *c, *to)).or_default();
+                    *count = (*count + n).min(2);
+                }
+            }
+        }
+
+        Graph {
+            start: nfa.q_start,
+            states,
+            accepting,
+            transitions,
+        }
+    }
+}
+
+/// Count the ways from `state` to every state reachable through epsilon transitions, without taking any of them twice.
+/// `used` are the epsilon transitions taken so far.
+fn epsilon_ways(
+    state: usize,
+    epsilon: &HashMap<usize, Vec<usize>>,
+    used: &mut HashSet<(usize, usize)>,
+    ways: &mut HashMap<usize, usize>,
+) {
+    let count = ways.entry(state).or_default();
+    *count = (*count + 1).min(2);
+    for to in epsilon.get(&state).into_iter().flatten() {
+        if used.insert((state, *to)) {
+            epsilon_ways(*to, epsilon, used, ways);
+            used.remove(&(state, *to));
+        }
+    }
+}
+
+impl Graph {
+    /// Remove all states that can't be reached from the start or can't reach an accepting state.
+    /// Paths through them never read a word to the end, so they are no matter of ambiguity.
+    fn trimmed(mut self) -> Graph {
+        let reachable = self.reachable(&[self.start]);
+        let mut coreachable: HashSet<usize> = self.accepting.clone();
+        // the reverse direction, until nothing new comes up
+        loop {
+            let more: HashSet<usize> = self
+                .transitions
+                .keys()
+                .filter(|(_, _, to)| coreachable.contains(to))
+                .map(|(from, _, _)| *from)
+                .collect();
+            if more.is_subset(&coreachable) {
+                break;
+            }
+            coreachable.extend(more);
+        }
+        let useful = |q: &usize| reachable.contains(q) && coreachable.contains(q);
+        self.states.retain(useful);
+        self.accepting.retain(useful);
+        self.transitions
+            .retain(|(from, _, to), _| useful(from) && useful(to));
+        self
+    }
+
+    fn successors(&self, state: usize) -> impl Iterator<Item = (char, usize)> + '_ {
+        self.transitions
+            .range((state, char::MIN, 0)..=(state, char::MAX, usize::MAX))
+            .map(|((_, c, to), _)| (*c, *to))
+    }
+
+    fn reachable(&self, from: &[usize]) -> HashSet<usize> {
+        let mut reachable: HashSet<usize> = from.iter().cloned().collect();
+        let mut queue: Vec<usize> = from.to_vec();
+        while let Some(state) = queue.pop() {
+            for (_, to) in self.successors(state) {
+                if reachable.insert(to) {
+                    queue.push(to);
+                }
+            }
+        }
+        reachable
+    }
+
+    /// The shortest word leading from `from` to `to`, which is empty if they are the same
+    fn word(&self, from: usize, to: usize) -> Option<String> {
+        shortest_word(from, |q| self.successors(*q).collect(), |q| *q == to, false)
+    }
+
+    /// The states reachable from `state` by reading `c`
+    fn successors_with(&self, state: usize, c: char) -> impl Iterator<Item = usize> + '_ {
+        self.transitions
+            .range((state, c, 0)..=(state, c, usize::MAX))
+            .map(|((_, _, to), _)| *to)
+    }
+
+    /// Pairs of states reachable from `(l, r)` by reading the same symbol
+    fn pair_successors(&self, (l, r): (usize, usize)) -> Vec<(char, (usize, usize))> {
+        self.successors(l)
+            .flat_map(|(c, l)| self.successors_with(r, c).map(move |r| (c, (l, r))))
+            .collect()
+    }
+
+    /// The shortest word with two different paths from some state back to itself, as an attack
+    fn exponential(&self) -> Option<Attack> {
+        // `(state, pump)` for every state with such a word
+        let mut candidates: Vec<(usize, String)> = Vec::new();
+
+        // a transition on a cycle that can be taken in two ways
+        for ((from, c, to), ways) in &self.transitions {
+            if *ways > 1
+                && let Some(back) = self.word(*to, *from)
+            {
+                candidates.push((*from, format!("{}{}", c, back)));
+            }
+        }
+
+        // two paths from `q` back to `q` reading the same word, which part somewhere on the way
+        // a pair of states both paths are in, and whether they have parted yet
+        type Node = ((usize, usize), bool);
+        let successors = |(pair, parted): &Node| -> Vec<(char, Node)> {
+            self.pair_successors(*pair)
+                .into_iter()
+                .map(|(c, (l, r))| (c, ((l, r), *parted || l != r)))
+                .collect()
+        };
+        for q in &self.states {
+            let goal = |node: &Node| *node == ((*q, *q), true);
+            if let Some(pump) = shortest_word(((*q, *q), false), successors, goal, true) {
+                candidates.push((*q, pump));
+            }
+        }
+
+        candidates
+            .into_iter()
+            .map(|(q, pump)| Attack {
+                prefix: self.word(self.start, q).unwrap(),
+                pumps: vec![(pump, String::new())],
+                suffix: String::new(),
+            })
+            .min_by_key(|attack| (attack.pumps[0].0.len(), attack.prefix.len()))
+    }
+
+    /// Paths from `p` to `p`, from `p` to `q` and from `q` to `q` reading the same word, for all `p != q`.
+    ///
+    /// Only ever called without an EDA, where `p` and `q` are in different strongly connected components: If `q` led
+    /// back to `p` reading `v`, `p -w-> p -w-> q -v-> p` and `p -w-> q -w-> q -v-> p` would be two paths from `p` back
+    /// to `p` reading `wwv`. The paths from `p` to `p` and from `q` to `q` don't leave their components, and the one
+    /// from `p` to `q` never leaves the states that can reach `q`, so the search is restricted to these.
+    fn ida(&self) -> Vec<(usize, usize, String)> {
+        let reachable: HashMap<usize, HashSet<usize>> = self
+            .states
+            .iter()
+            .map(|q| (*q, self.reachable(&[*q])))
+            .collect();
+        // the strongly connected component of every state on a cycle, no other state can have paths back to itself
+        let components: HashMap<usize, HashSet<usize>> = self
+            .states
+            .iter()
+            .filter(|q| {
+                self.successors(**q)
+                    .any(|(_, to)| reachable[&to].contains(q))
+            })
+            .map(|q| {
+                let component = reachable[q]
+                    .iter()
+                    .filter(|r| reachable[*r].contains(q))
+                    .cloned()
+                    .collect();
+                (*q, component)
+            })
+            .collect();
+
+        let mut ida = Vec::new();
+        for (p, from) in &components {
+            for (q, to) in &components {
+                if !reachable[p].contains(q) || reachable[q].contains(p) {
+                    continue;
+                }
+                let successors =
+                    |(x, y, z): &(usize, usize, usize)| -> Vec<(char, (usize, usize, usize))> {
+                        let mut successors = Vec::new();
+                        for (c, x) in self.successors(*x).filter(|(_, x)| from.contains(x)) {
+                            for y in self
+                                .successors_with(*y, c)
+                                .filter(|y| reachable[y].contains(q))
+                            {
+                                for z in self.successors_with(*z, c).filter(|z| to.contains(z)) {
+                                    successors.push((c, (x, y, z)));
+                                }
+                            }
+                        }
+                        successors
+                    };
+                if let Some(w) =
+                    shortest_word((*p, *p, *q), successors, |t| *t == (*p, *q, *q), true)
+                {
+                    ida.push((*p, *q, w));
+                }
+            }
+        }
+        // in a fixed order, so ties between chains of the same length are always broken the same way
+        ida.sort();
+        ida
+    }
+
+    /// The longest chain of IDAs, each reachable from the one before it, as the degree of the ambiguity
+    fn polynomial(&self) -> Option<(usize, Attack)> {
+        let ida = self.ida();
+        let reachable: HashMap<usize, HashSet<usize>> = ida
+            .iter()
+            .map(|(_, q, _)| (*q, self.reachable(&[*q])))
+            .collect();
+        // without an EDA, the IDAs can't form a cycle, so this terminates
+        fn longest(
+            i: usize,
+            ida: &[(usize, usize, String)],
+            reachable: &HashMap<usize, HashSet<usize>>,
+            memo: &mut HashMap<usize, Vec<usize>>,
+        ) -> Vec<usize> {
+            if let Some(chain) = memo.get(&i) {
+                return chain.clone();
+            }
+            let mut chain = vec![i];
+            for j in 0..ida.len() {
+                if reachable[&ida[i].1].contains(&ida[j].0) {
+                    let rest = longest(j, ida, reachable, memo);
+                    if rest.len() >= chain.len() {
+                        chain = [vec![i], rest].concat();
+                    }
+                }
+            }
+            memo.insert(i, chain.clone());
+            chain
+        }
+
+        let mut memo = HashMap::new();
+        let chain = (0..ida.len())
+            .map(|i| longest(i, &ida, &reachable, &mut memo))
+            .reduce(|longest, chain| {
+                if chain.len() > longest.len() {
+                    chain
+                } else {
+                    longest
+                }
+            })?;
+
+        let (first, _, _) = &ida[chain[0]];
+        let pumps = chain
+            .iter()
+            .enumerate()
+            .map(|(k, i)| {
+                let (_, q, w) = &ida[*i];
+                let then = match chain.get(k + 1) {
+                    Some(next) => self.word(*q, ida[*next].0).unwrap(),
+                    None => String::new(),
+                };
+                (w.clone(), then)
+            })
+            .collect();
+        let attack = Attack {
+            prefix: self.word(self.start, *first).unwrap(),
+            pumps,
+            suffix: String::new(),
+        };
+        Some((chain.len(), attack))
+    }
+}
+
+/// Breadth first search for the shortest word leading from `start` to a node satisfying `goal`, lexicographically
+/// smallest among words of the same length.
+/// `successors` of a node have to be sorted by their symbol. Unless `nonempty`, `start` itself may be the goal.
+fn shortest_word<N, S, G>(start: N, successors: S, goal: G, nonempty: bool) -> Option<String>
+where
+    N: Clone + Eq + Hash,
+    S: Fn(&N) -> Vec<(char, N)>,
+    G: Fn(&N) -> bool,
+{
+    if !nonempty && goal(&start) {
+        return Some(String::new());
+    }
+    // remembering how every node was reached to rebuild the word afterwards
+    let mut reached_by: HashMap<N, (N, char)> = HashMap::new();
+    let mut visited: HashSet<N> = HashSet::from([start.clone()]);
+    let mut queue: VecDeque<N> = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        for (c, next) in successors(&node) {
+            if goal(&next) {
+                let mut word = vec![c];
+                let mut current = node;
+                while let Some((previous, c)) = reached_by.get(&current) {
+                    word.push(*c);
+                    current = previous.clone();
+                }
+                return Some(word.iter().rev().collect());
+            }
+            if visited.insert(next.clone()) {
+                reached_by.insert(next.clone(), (node.clone(), c));
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,349 @@
+use crate::automata::{Automaton, Deterministic, Dfa, Symbol, find_all, next_state};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+//////////////////////////////////////////////////// BINARY FORMAT ///////////////////////////////////////////////////
+// Compiled `Dfa`s are stored as a dense transition table, so they can be cached on disk instead of being
+// recompiled on every start. All integers are little endian, regardless of the platform.
+//
+// | offset         | size         | content                                                          |
+// |----------------|--------------|------------------------------------------------------------------|
+// | 0              | 4            | magic bytes `RDFA`                                               |
+// | 4              | 2            | format version (u16)                                             |
+// | 6              | 2            | reserved, always 0                                               |
+// | 8              | 4            | number of states `n` (u32)                                       |
+// | 12             | 4            | number of symbols `s` (u32)                                      |
+// | 16             | 4            | index of the start state (u32)                                   |
+// | 20             | 4s           | symbols as unicode scalar values, strictly ascending (u32)       |
+// | 20 + 4s        | n            | 1 if the state is accepting, 0 otherwise (u8)                    |
+// | 20 + 4s + n    | 4ns          | transition table, row by row: index of the target state or       |
+// |                |              | `u32::MAX` if there's no transition for that symbol (u32)        |
+// | 20 + 4s + 5ns  | 4            | CRC-32 of everything before it (u32)                             |
+//
+// States are renumbered to `0..n` when writing, the original ids are not preserved.
+
+pub(crate) const MAGIC: &[u8; 4] = b"RDFA";
+pub(crate) const VERSION: u16 = 1;
+pub(crate) const HEADER_LEN: usize = 20;
+/// Marks a missing transition in the table
+pub(crate) const NO_TRANSITION: u32 = u32::MAX;
+
+impl Dfa {
+    /// Serialize `self` into the binary format described in `binary.rs`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut states = self.states.clone();
+        states.sort();
+        let index: HashMap<usize, u32> = states
+            .iter()
+            .enumerate()
+            .map(|(i, q)| (*q, i as u32))
+            .collect();
+        // a valid dfa has nothing but chars in its alphabet
+        let mut symbols: Vec<char> = self
+            .alphabet
+            .iter()
+            .filter_map(|s| match s {
+                Symbol::CHAR(c) => Some(*c),
+                _ => None,
+            })
+            .collect();
+        symbols.sort();
+
+        let mut bytes: Vec<u8> = Vec::with_capacity(
+            HEADER_LEN + 4 * symbols.len() + 5 * states.len() * symbols.len() + 4,
+        );
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bytes.extend_from_slice(&0u16.to_le_bytes());
+        bytes.extend_from_slice(&(states.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&(symbols.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&index[&self.q_start].to_le_bytes());
+        for c in &symbols {
+            bytes.extend_from_slice(&(*c as u32).to_le_bytes());
+        }
+        for q in &states {
+            bytes.push(self.q_accepting.contains(q) as u8);
+        }
+        for q in &states {
+            for c in &symbols {
+                let target = match self.transitions.get(&(*q, Symbol::CHAR(*c))) {
+                    Some(to) => index[to],
+                    None => NO_TRANSITION,
+                };
+                bytes.extend_from_slice(&target.to_le_bytes());
+            }
+        }
+        let checksum = crc32(&bytes);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+        bytes
+    }
+
+    /// Deserialize a `Dfa` previously serialized with `Dfa::to_bytes`.
+    /// The states of the result get fresh ids, so it can safely be combined with other automata.
+    /// returns: Err(reason) if `bytes` is corrupted, of an unsupported version or describes an invalid `Dfa`
+    pub fn from_bytes(bytes: &[u8]) -> Result<Dfa, String> {
+        let layout = Layout::read(bytes)?;
+        layout.verify(bytes)?;
+
+        let ids: Vec<usize> = (0..layout.states).map(|_| next_state()).collect();
+        let mut transitions: HashMap<(usize, Symbol), usize> = HashMap::new();
+        let mut q_accepting: HashSet<usize> = HashSet::new();
+        for (i, id) in ids.iter().enumerate() {
+            if layout.accepting(bytes, i) {
+                q_accepting.insert(*id);
+            }
+            for j in 0..layout.symbols {
+                if let Some(to) = layout.target(bytes, i, j) {
+                    transitions.insert((*id, Symbol::CHAR(layout.symbol(bytes, j))), ids[to]);
+                }
+            }
+        }
+
+        let mut dfa = Dfa::new(ids.clone(), transitions, ids[layout.q_start], q_accepting);
+        dfa.alphabet = (0..layout.symbols)
+            .map(|j| Symbol::CHAR(layout.symbol(bytes, j)))
+            .collect();
+        dfa.validate()?;
+        Ok(dfa)
+    }
+}
+
+///////////////////////////////////////////////////// ZERO-COPY /////////////////////////////////////////////////////
+
+/// A serialized `Dfa` that is run directly on the bytes it was written to, e.g. a memory mapped file or an
+/// `include_bytes!`. Apart from the checks of `DfaRef::new`, nothing needs to be built up before matching, and
+/// `DfaRef::new_unchecked` skips the ones that take as long as the automaton is large.
+///
+/// States are the dense indices of the binary format rather than the ids of the original `Dfa`.
+/// Looking up a symbol is a binary search over the symbol section, so this is a bit slower per character than
+/// a `Dfa`, in exchange for not paying the construction at all.
+pub struct DfaRef<'a> {
+    bytes: &'a [u8],
+    layout: Layout,
+}
+
+impl<'a> DfaRef<'a> {
+    /// Check `bytes` (see `Dfa::from_bytes`) and borrow them for matching.
+    pub fn new(bytes: &'a [u8]) -> Result<DfaRef<'a>, String> {
+        let dfa = DfaRef::new_unchecked(bytes)?;
+        dfa.verify()?;
+        Ok(dfa)
+    }
+
+    /// Borrow `bytes` for matching after checking nothing but the header, the size and the symbols, so start-up
+    /// doesn't depend on the number of states. The checksum and the transition table are left to `DfaRef::verify`.
+    /// Matching never panics on corrupted bytes, but its results are meaningless until they have been verified.
+    pub fn new_unchecked(bytes: &'a [u8]) -> Result<DfaRef<'a>, String> {
+        let layout = Layout::read(bytes)?;
+        Ok(DfaRef { bytes, layout })
+    }
+
+    /// Check the checksum and the transition table, which `DfaRef::new` does right away.
+    pub fn verify(&self) -> Result<(), String> {
+        self.layout.verify(self.bytes)
+    }
+
+    /// Copy `self` into an owned `Dfa`, which fails like `Dfa::from_bytes` for bytes that haven't been verified yet.
+    pub fn to_dfa(&self) -> Result<Dfa, String> {
+        Dfa::from_bytes(self.bytes)
+    }
+
+    /// Find all matches of the pattern in `input`, see `Dfa::find_all`.
+    /// As with `Dfa`, `self` has to be the reversed pattern and `input` the reversed word.
+    pub fn find_all(&self, input: &str, reversed: &DfaRef) -> Option<Vec<(usize, usize)>> {
+        find_all(self, input, reversed)
+    }
+
+    /// Find the first match of the pattern in `input`, see `Dfa::find`.
+    pub fn find(&self, input: &str, reversed: &DfaRef) -> Option<(usize, usize)> {
+        self.find_all(input, reversed)?.first().cloned()
+    }
+}
+
+impl Deterministic for DfaRef<'_> {
+    fn start(&self) -> usize {
+        self.layout.q_start
+    }
+
+    fn step(&self, state: usize, c: char) -> Option<usize> {
+        let j = self.layout.symbol_index(self.bytes, c)?;
+        self.layout.target(self.bytes, state, j)
+    }
+
+    fn is_accepting(&self, state: usize) -> bool {
+        self.layout.accepting(self.bytes, state)
+    }
+}
+
+impl Automaton for DfaRef<'_> {
+    /// See `DfaRef::verify`, which is all `DfaRef::new_unchecked` leaves out.
+    fn validate(&self) -> Result<(), String> {
+        self.verify()
+    }
+
+    fn accept(&self, input: &str) -> bool {
+        let mut current = self.start();
+        for c in input.chars() {
+            match self.step(current, c) {
+                Some(next) => current = next,
+                None => return false,
+            }
+        }
+        self.is_accepting(current)
+    }
+}
+
+/// Positions of the individual sections of a serialized `Dfa`, after checking that they make sense.
+pub(crate) struct Layout {
+    pub states: usize,
+    pub symbols: usize,
+    pub q_start: usize,
+    symbols_at: usize,
+    accepting_at: usize,
+    table_at: usize,
+}
+
+impl Layout {
+    /// Check the header, size and symbols of `bytes`, so all accessors can be used without further bounds checks
+    /// afterwards. That takes as long as there are symbols, see `Layout::verify` for the rest.
+    pub fn read(bytes: &[u8]) -> Result<Layout, String> {
+        if bytes.len() < HEADER_LEN + 4 || &bytes[0..4] != MAGIC {
+            return Err(String::from("Not a serialized Dfa"));
+        }
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if version != VERSION {
+            return Err(format!(
+                "Unsupported format version {} (expected {})",
+                version, VERSION
+            ));
+        }
+        if bytes[6..8] != [0, 0] {
+            return Err(String::from("Reserved header bytes are not 0"));
+        }
+        let states = read_u32(bytes, 8) as usize;
+        let symbols = read_u32(bytes, 12) as usize;
+        let q_start = read_u32(bytes, 16) as usize;
+
+        // checked, as the counts come straight out of a potentially corrupted file
+        let expected_len = states
+            .checked_mul(symbols)
+            .and_then(|cells| cells.checked_mul(4))
+            .and_then(|table| table.checked_add(HEADER_LEN + 4 + states))
+            .and_then(|len| len.checked_add(symbols.checked_mul(4)?));
+        if expected_len != Some(bytes.len()) {
+            return Err(format!(
+                "Expected {} bytes for {} states and {} symbols, got {}",
+                expected_len.map_or(String::from("too many"), |len| len.to_string()),
+                states,
+                symbols,
+                bytes.len()
+            ));
+        }
+        let layout = Layout {
+            states,
+            symbols,
+            q_start,
+            symbols_at: HEADER_LEN,
+            accepting_at: HEADER_LEN + 4 * symbols,
+            table_at: HEADER_LEN + 4 * symbols + states,
+        };
+        if q_start >= states {
+            return Err(String::from("q_0 ∉ Q"));
+        }
+        let mut previous: Option<u32> = None;
+        for j in 0..symbols {
+            let code = read_u32(bytes, layout.symbols_at + 4 * j);
+            if char::from_u32(code).is_none() || previous.is_some_and(|p| p >= code) {
+                return Err(format!("Invalid or unordered symbol {:#x}", code));
+            }
+            previous = Some(code);
+        }
+        Ok(layout)
+    }
+
+    /// Check the checksum, the accepting flags and the transition table of `bytes`, which takes as long as the
+    /// automaton is large.
+    pub fn verify(&self, bytes: &[u8]) -> Result<(), String> {
+        let checksum_at = bytes.len() - 4;
+        if crc32(&bytes[..checksum_at]) != read_u32(bytes, checksum_at) {
+            return Err(String::from("Checksum mismatch"));
+        }
+        if bytes[self.accepting_at..self.table_at]
+            .iter()
+            .any(|b| *b > 1)
+        {
+            return Err(String::from("Invalid accepting flag"));
+        }
+        for cell in 0..self.states * self.symbols {
+            let target = read_u32(bytes, self.table_at + 4 * cell);
+            if target != NO_TRANSITION && target as usize >= self.states {
+                return Err(format!("Transition to unknown state {}", target));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn symbol(&self, bytes: &[u8], j: usize) -> char {
+        char::from_u32(read_u32(bytes, self.symbols_at + 4 * j)).unwrap()
+    }
+
+    /// Binary search for the index of `c` in the (strictly ascending) symbol section
+    pub fn symbol_index(&self, bytes: &[u8], c: char) -> Option<usize> {
+        let (mut low, mut high) = (0, self.symbols);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let code = read_u32(bytes, self.symbols_at + 4 * mid);
+            match code.cmp(&(c as u32)) {
+                Ordering::Equal => return Some(mid),
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+            }
+        }
+        None
+    }
+
+    pub fn accepting(&self, bytes: &[u8], state: usize) -> bool {
+        bytes[self.accepting_at + state] == 1
+    }
+
+    /// The target of the transition of `state` with the `j`th symbol. An unknown target, which only an unverified
+    /// table can contain, is treated like a missing transition.
+    pub fn target(&self, bytes: &[u8], state: usize, j: usize) -> Option<usize> {
+        match read_u32(bytes, self.table_at + 4 * (state * self.symbols + j)) {
+            NO_TRANSITION => None,
+            target if target as usize >= self.states => None,
+            target => Some(target as usize),
+        }
+    }
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+}
+
+/// The CRC-32 of every single byte, so `crc32` can process a byte at a time instead of a bit
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// CRC-32 (IEEE 802.3, as used by zip and png)
+pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc: u32 = 0xFFFF_FFFF;
+    for byte in bytes {
+        crc = (crc >> 8) ^ CRC32_TABLE[((crc ^ *byte as u32) & 0xFF) as usize];
+    }
+    !crc
+}
@@ -0,0 +1,122 @@
+use crate::automata::{Dfa, Symbol, next_state};
+use bimap::BiMap;
+use std::collections::{HashMap, HashSet};
+
+/////////////////////////////////////////////// BOOLEAN OPERATIONS /////////////////////////////////////////////////
+// All of these are based on the product construction: run both automata in lockstep and decide
+// for every pair of states whether it should be accepting.
+// For this to work both automata must be total over the same alphabet, otherwise a run of one automaton
+// dying would kill the run of the product as well (which is wrong for everything but the intersection).
+
+impl Dfa {
+    /// Construct an equivalent `Dfa` which has a transition for every symbol of `alphabet` in every state.
+    /// Missing transitions are redirected into a (non-accepting) dead state, which is only added if needed.
+    /// The alphabet of the result is the union of `alphabet` and the alphabet of `self`.
+    pub fn totalize(&self, alphabet: &HashSet<Symbol>) -> Dfa {
+        let alphabet: HashSet<Symbol> = self.alphabet.union(alphabet).cloned().collect();
+        let mut dfa = Dfa::new(
+            self.states.clone(),
+            self.transitions.clone(),
+            self.q_start,
+            self.q_accepting.clone(),
+        );
+        dfa.alphabet = alphabet;
+        // the dead state stands for the empty set, which it doesn't need to be told
+        dfa.nfa_states = self.nfa_states.clone();
+
+        let mut dead_state: Option<usize> = None;
+        for state in &self.states {
+            for symbol in &dfa.alphabet {
+                if dfa.transitions.contains_key(&(*state, *symbol)) {
+                    continue;
+                }
+                let dead = *dead_state.get_or_insert_with(next_state);
+                dfa.transitions.insert((*state, *symbol), dead);
+            }
+        }
+        if let Some(dead) = dead_state {
+            dfa.states.push(dead);
+            for symbol in &dfa.alphabet {
+                dfa.transitions.insert((dead, *symbol), dead);
+            }
+        }
+        dfa
+    }
+
+    /// Construct a `Dfa` accepting exactly the words over `alphabet` (plus the alphabet of `self`)
+    /// that are **not** accepted by `self`.
+    ///
+    /// As a `Dfa` only knows the symbols it has transitions for, the complement would otherwise be
+    /// ill-defined: Complementing `a` over `{a}` does not accept `b`, complementing it over `{a, b}` does.
+    pub fn complement(&self, alphabet: &HashSet<Symbol>) -> Dfa {
+        let mut dfa = self.totalize(alphabet);
+        dfa.q_accepting = dfa
+            .states
+            .iter()
+            .filter(|q| !self.q_accepting.contains(q))
+            .cloned()
+            .collect();
+        dfa
+    }
+
+    /// Construct a `Dfa` accepting the words accepted by both `self` and `other`.
+    pub fn intersect(&self, other: &Dfa) -> Dfa {
+        self.product(other, |l, r| l && r)
+    }
+
+    /// Construct a `Dfa` accepting the words accepted by `self`, but not by `other`.
+    pub fn difference(&self, other: &Dfa) -> Dfa {
+        self.product(other, |l, r| l && !r)
+    }
+
+    /// Construct a `Dfa` accepting the words accepted by exactly one of `self` and `other`.
+    pub fn symmetric_difference(&self, other: &Dfa) -> Dfa {
+        self.product(other, |l, r| l != r)
+    }
+
+    /// Product construction of `self` and `other` over the union of both alphabets.
+    /// A pair of states is accepting iff `accepting(left is accepting, right is accepting)` holds.
+    fn product(&self, other: &Dfa, accepting: impl Fn(bool, bool) -> bool) -> Dfa {
+        let alphabet: HashSet<Symbol> = self.alphabet.union(&other.alphabet).cloned().collect();
+        let left = self.totalize(&alphabet);
+        let right = other.totalize(&alphabet);
+
+        let new_q0 = (left.q_start, right.q_start);
+        let new_q0_id = next_state();
+        let mut id_to_pair: BiMap<usize, (usize, usize)> = BiMap::new();
+        id_to_pair.insert(new_q0_id, new_q0);
+
+        let mut dfa = Dfa::new(vec![new_q0_id], HashMap::new(), new_q0_id, HashSet::new());
+        dfa.alphabet = alphabet;
+
+        // same worklist approach as in the powerset construction: `dfa.states` doubles as the queue
+        let mut i: usize = 0;
+        while let Some(state) = dfa.states.get(i).cloned() {
+            let (l, r) = *id_to_pair.get_by_left(&state).unwrap();
+            if accepting(
+                left.q_accepting.contains(&l),
+                right.q_accepting.contains(&r),
+            ) {
+                dfa.q_accepting.insert(state);
+            }
+            for symbol in &dfa.alphabet {
+                // both are total, so this can't fail
+                let target = (
+                    left.transitions[&(l, *symbol)],
+                    right.transitions[&(r, *symbol)],
+                );
+                let to = if let Some(id) = id_to_pair.get_by_right(&target) {
+                    *id
+                } else {
+                    let new_state = next_state();
+                    dfa.states.push(new_state);
+                    id_to_pair.insert(new_state, target);
+                    new_state
+                };
+                dfa.transitions.insert((state, *symbol), to);
+            }
+            i += 1;
+        }
+        dfa
+    }
+}
@@ -0,0 +1,56 @@
+use crate::automata::Dfa;
+use std::collections::HashMap;
+
+//////////////////////////////////////////////////// WORD COUNTING ///////////////////////////////////////////////////
+// Number of accepted words per length, via dynamic programming over the transition table:
+// Let `f_k(q)` be the number of words of length `k` accepted when starting the run in `q`. Then
+//      f_0(q) = 1 if q ∈ F, 0 otherwise
+//      f_k(q) = Σ f_{k-1}(δ(q, a)) over all symbols `a` with a transition out of `q`
+// And the number of accepted words of length `k` is `f_k(q_0)`.
+// Counts are `u128` and saturate at `u128::MAX` instead of overflowing, as e.g. `(a|b)*` has 2^k words of length k.
+
+impl Dfa {
+    /// Number of accepted words of exactly `len` symbols (saturating at `u128::MAX`).
+    pub fn count_words(&self, len: usize) -> u128 {
+        self.count_table(len)[len][&self.q_start]
+    }
+
+    /// Number of accepted words of at most `len` symbols (saturating at `u128::MAX`).
+    pub fn count_up_to(&self, len: usize) -> u128 {
+        self.count_table(len)
+            .iter()
+            .map(|counts| counts[&self.q_start])
+            .fold(0, u128::saturating_add)
+    }
+
+    /// Total number of accepted words if the language of `self` is finite, `None` otherwise.
+    pub fn cardinality(&self) -> Option<u128> {
+        if !self.is_finite() {
+            return None;
+        }
+        // a word longer than |Q| - 1 would have to visit some state twice, i.e. it would run through a cycle
+        Some(self.count_up_to(self.states.len().saturating_sub(1)))
+    }
+
+    /// `table[k][q]` is the number of words of length `k` accepted when starting the run in `q`,
+    /// for every `k` from `0` up to and including `len`.
+    pub(super) fn count_table(&self, len: usize) -> Vec<HashMap<usize, u128>> {
+        let mut table: Vec<HashMap<usize, u128>> = Vec::with_capacity(len + 1);
+        table.push(
+            self.states
+                .iter()
+                .map(|q| (*q, self.q_accepting.contains(q) as u128))
+                .collect(),
+        );
+        for k in 1..=len {
+            let previous = &table[k - 1];
+            let mut counts: HashMap<usize, u128> = self.states.iter().map(|q| (*q, 0)).collect();
+            for ((from, _), to) in &self.transitions {
+                let count = counts.get_mut(from).unwrap();
+                *count = count.saturating_add(previous[to]);
+            }
+            table.push(counts);
+        }
+        table
+    }
+}
@@ -0,0 +1,133 @@
+use crate::automata::{Dfa, Symbol};
+use std::collections::{HashMap, HashSet};
+
+///////////////////////////////////////////////////// ENUMERATION ////////////////////////////////////////////////////
+
+impl Dfa {
+    /// Lazily iterate over all words accepted by `self` in shortlex order, i.e. ordered by length first and
+    /// lexicographically (by `char`) second. Only words of at most `max_len` symbols are produced, if given.
+    ///
+    /// Note that for an infinite language without `max_len`, this iterator never ends.
+    pub fn words(&self, max_len: Option<usize>) -> Words<'_> {
+        let successors = self.sorted_successors();
+        // a word longer than |Q| - 1 would have to run through a cycle
+        let longest = if self.is_finite() {
+            self.states.len().saturating_sub(1)
+        } else {
+            usize::MAX
+        };
+
+        Words {
+            dfa: self,
+            successors,
+            viable: vec![self.q_accepting.clone()],
+            max_len: max_len.unwrap_or(usize::MAX).min(longest),
+            next_len: 0,
+            len: 0,
+            stack: Vec::new(),
+            word: Vec::new(),
+        }
+    }
+
+    /// The symbols and targets of the transitions leaving every state, sorted by symbol. Walking them in this order
+    /// visits words lexicographically, and stays reproducible, as hashmaps iterate in random order.
+    pub(super) fn sorted_successors(&self) -> HashMap<usize, Vec<(char, usize)>> {
+        let mut successors: HashMap<usize, Vec<(char, usize)>> = HashMap::new();
+        for ((from, with), to) in &self.transitions {
+            if let Symbol::CHAR(c) = with {
+                successors.entry(*from).or_default().push((*c, *to));
+            }
+        }
+        for targets in successors.values_mut() {
+            targets.sort();
+        }
+        successors
+    }
+}
+
+/// Iterator over the words accepted by a `Dfa` in shortlex order, see `Dfa::words`.
+///
+/// Words of a single length are produced by a depth-first search through the `Dfa`, taking successors in
+/// lexicographic order. Only states from which the run can still end up accepting with exactly the number of
+/// remaining symbols are visited, so every leaf reached by the search is an accepted word.
+pub struct Words<'a> {
+    dfa: &'a Dfa,
+    /// successors of every state, sorted by symbol
+    successors: HashMap<usize, Vec<(char, usize)>>,
+    /// `viable[k]` is the set of states from which some word of exactly `k` symbols is accepted
+    viable: Vec<HashSet<usize>>,
+    max_len: usize,
+    next_len: usize,
+    /// length of the words currently produced
+    len: usize,
+    /// dfs stack of `(state, index of the next successor to try)`
+    stack: Vec<(usize, usize)>,
+    /// word leading from `q_0` to the state on top of `stack`
+    word: Vec<char>,
+}
+
+impl Words<'_> {
+    /// Move on to the next length that has any accepted words at all.
+    /// returns: false if there are no more words to produce
+    fn advance(&mut self) -> bool {
+        while self.next_len <= self.max_len {
+            self.len = self.next_len;
+            self.next_len += 1;
+            while self.viable.len() <= self.len {
+                let previous = self.viable.last().unwrap();
+                let viable: HashSet<usize> = self
+                    .dfa
+                    .transitions
+                    .iter()
+                    .filter(|(_, to)| previous.contains(to))
+                    .map(|((from, _), _)| *from)
+                    .collect();
+                self.viable.push(viable);
+            }
+            if self.viable[self.len].contains(&self.dfa.q_start) {
+                self.stack.push((self.dfa.q_start, 0));
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl Iterator for Words<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        loop {
+            let Some((state, i)) = self.stack.pop() else {
+                // all words of the current length have been produced
+                if !self.advance() {
+                    return None;
+                }
+                continue;
+            };
+            let depth = self.word.len();
+            if depth == self.len {
+                let word = self.word.iter().collect();
+                self.word.pop();
+                return Some(word);
+            }
+
+            let remaining = self.len - depth - 1;
+            let next = self.successors.get(&state).and_then(|successors| {
+                successors
+                    .iter()
+                    .enumerate()
+                    .skip(i)
+                    .find(|(_, (_, to))| self.viable[remaining].contains(to))
+            });
+            if let Some((j, (c, to))) = next {
+                self.stack.push((state, j + 1));
+                self.stack.push((*to, 0));
+                self.word.push(*c);
+            } else {
+                // every successor has been visited, go back up
+                self.word.pop();
+            }
+        }
+    }
+}
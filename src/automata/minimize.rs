@@ -0,0 +1,125 @@
+use crate::automata::{Dfa, next_state};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+//////////////////////////////////////////////////// MINIMIZATION ////////////////////////////////////////////////////
+// Moore's algorithm: start with the partition {accepting, non-accepting} and keep splitting every block whose states
+// disagree on which block a symbol leads to, until nothing changes anymore. States that end up in the same block are
+// indistinguishable and become a single state of the minimal `Dfa`.
+// A missing transition kills the run, just like a transition into a dead state would. So dead states are removed
+// beforehand and missing transitions are compared as if they led into an extra block of their own, which makes the
+// result minimal among all `Dfa`s without dead states.
+
+impl Dfa {
+    /// Construct the `Dfa` with the fewest states accepting the same language as `self`.
+    /// Unreachable states and states from which no accepting state can be reached are dropped, so the result
+    /// doesn't have transitions for every symbol in every state. It keeps the alphabet of `self` though, and
+    /// merged states stand for all the `nfa_states` of the states they were merged from.
+    pub fn minimize(&self) -> Dfa {
+        let productive = self.productive_states();
+        let symbols: Vec<_> = self.alphabet.iter().cloned().collect();
+        let target = |state: usize, j: usize| {
+            self.transitions
+                .get(&(state, symbols[j]))
+                .filter(|to| productive.contains(to))
+        };
+
+        // breadth first order over the states that are reachable while still being able to accept,
+        // so states of the result are numbered in the order they are reached
+        let mut order: Vec<usize> = Vec::new();
+        let mut visited: HashSet<usize> = HashSet::new();
+        let mut queue: VecDeque<usize> = VecDeque::new();
+        if productive.contains(&self.q_start) {
+            visited.insert(self.q_start);
+            queue.push_back(self.q_start);
+        }
+        while let Some(state) = queue.pop_front() {
+            order.push(state);
+            for j in 0..symbols.len() {
+                if let Some(to) = target(state, j)
+                    && visited.insert(*to)
+                {
+                    queue.push_back(*to);
+                }
+            }
+        }
+
+        let mut block: HashMap<usize, usize> = order
+            .iter()
+            .map(|q| (*q, usize::from(!self.q_accepting.contains(q))))
+            .collect();
+        let mut blocks = 0;
+        loop {
+            // a state's signature is its own block and the blocks of all its successors, `None` being the dead block
+            let mut ids: HashMap<(usize, Vec<Option<usize>>), usize> = HashMap::new();
+            let refined: HashMap<usize, usize> = order
+                .iter()
+                .map(|q| {
+                    let signature = (
+                        block[q],
+                        (0..symbols.len())
+                            .map(|j| target(*q, j).map(|to| block[to]))
+                            .collect(),
+                    );
+                    let next = ids.len();
+                    (*q, *ids.entry(signature).or_insert(next))
+                })
+                .collect();
+            block = refined;
+            // blocks are only ever split, so the partition is stable once their number stays the same
+            if ids.len() == blocks {
+                break;
+            }
+            blocks = ids.len();
+        }
+
+        let ids: Vec<usize> = (0..blocks).map(|_| next_state()).collect();
+        let mut dfa = match order.first() {
+            Some(start) => Dfa::new(
+                ids.clone(),
+                HashMap::new(),
+                ids[block[start]],
+                HashSet::new(),
+            ),
+            // the language is empty, which takes a single rejecting state
+            None => {
+                let q0 = next_state();
+                Dfa::new(vec![q0], HashMap::new(), q0, HashSet::new())
+            }
+        };
+        for state in &order {
+            let from = ids[block[state]];
+            if self.q_accepting.contains(state) {
+                dfa.q_accepting.insert(from);
+            }
+            // a merged state stands for all the nfa states its members did
+            if let Some(nfa_states) = self.nfa_states.get(state) {
+                dfa.nfa_states.entry(from).or_default().extend(nfa_states);
+            }
+            for (j, symbol) in symbols.iter().enumerate() {
+                if let Some(to) = target(*state, j) {
+                    dfa.transitions.insert((from, *symbol), ids[block[to]]);
+                }
+            }
+        }
+        dfa.alphabet = self.alphabet.clone();
+        dfa
+    }
+
+    /// All states from which an accepting state can be reached.
+    fn productive_states(&self) -> HashSet<usize> {
+        let mut predecessors: HashMap<usize, Vec<usize>> = HashMap::new();
+        for ((from, _), to) in &self.transitions {
+            predecessors.entry(*to).or_default().push(*from);
+        }
+        let mut productive: HashSet<usize> = HashSet::new();
+        let mut stack: Vec<usize> = self.q_accepting.iter().cloned().collect();
+        while let Some(state) = stack.pop() {
+            if productive.insert(state)
+                && let Some(from) = predecessors.get(&state)
+            {
+                stack.extend(from);
+            }
+        }
+        productive
+    }
+}
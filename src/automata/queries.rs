@@ -0,0 +1,185 @@
+use crate::automata::{Dfa, Nfa, Symbol};
+use std::collections::{HashMap, HashSet};
+
+/////////////////////////////////////////////// STRUCTURAL QUERIES /////////////////////////////////////////////////
+// Questions about the language of an automaton that can be answered by only looking at its graph,
+// without ever simulating a run on a word.
+
+impl Nfa {
+    /// Whether `self` accepts the empty word, i.e. whether an accepting state is in the epsilon closure of `q_0`.
+    pub fn accepts_empty_word(&self) -> bool {
+        self.ec(self.q_start)
+            .iter()
+            .any(|q| self.q_accepting.contains(q))
+    }
+
+    /// Whether the language of `self` is empty, i.e. no accepting state is reachable from `q_0`.
+    pub fn is_empty(&self) -> bool {
+        let edges = self.edges();
+        let reachable = reachable(&edges, [self.q_start]);
+        !self.q_accepting.iter().any(|q| reachable.contains(q))
+    }
+
+    /// Whether `self` accepts every word over `alphabet`.
+    /// Requires a powerset construction, so this is anything but cheap.
+    pub fn is_universal(&self, alphabet: &HashSet<Symbol>) -> bool {
+        Dfa::from(self).is_universal(alphabet)
+    }
+
+    /// Whether the language of `self` is finite.
+    /// It is infinite iff there's a cycle consuming at least one symbol through states which are both reachable
+    /// from `q_0` and can reach an accepting state themselves. Pure epsilon-cycles don't count.
+    pub fn is_finite(&self) -> bool {
+        !has_productive_cycle(&self.edges(), self.q_start, &self.q_accepting)
+    }
+
+    /// Transitions as `(from, consumes a symbol, to)`
+    fn edges(&self) -> Vec<(usize, bool, usize)> {
+        self.transitions
+            .iter()
+            .map(|(from, with, to)| (*from, *with != Symbol::EPSILON, *to))
+            .collect()
+    }
+}
+
+impl Dfa {
+    /// Whether `self` accepts the empty word, i.e. whether `q_0` is accepting.
+    pub fn accepts_empty_word(&self) -> bool {
+        self.q_accepting.contains(&self.q_start)
+    }
+
+    /// Whether the language of `self` is empty, i.e. no accepting state is reachable from `q_0`.
+    pub fn is_empty(&self) -> bool {
+        let reachable = reachable(&self.edges(), [self.q_start]);
+        !self.q_accepting.iter().any(|q| reachable.contains(q))
+    }
+
+    /// Whether `self` accepts every word over `alphabet` (and the symbols `self` has transitions for).
+    pub fn is_universal(&self, alphabet: &HashSet<Symbol>) -> bool {
+        self.complement(alphabet).is_empty()
+    }
+
+    /// Whether the language of `self` is finite.
+    /// It is infinite iff there's a cycle through states which are both reachable from `q_0` and can reach
+    /// an accepting state themselves.
+    pub fn is_finite(&self) -> bool {
+        !has_productive_cycle(&self.edges(), self.q_start, &self.q_accepting)
+    }
+
+    /// Transitions as `(from, consumes a symbol, to)`
+    fn edges(&self) -> Vec<(usize, bool, usize)> {
+        self.transitions
+            .iter()
+            .map(|((from, _), to)| (*from, true, *to))
+            .collect()
+    }
+}
+
+///////////////////////////////////////////////////// GRAPH HELPERS ////////////////////////////////////////////////////
+
+/// All states reachable from any of `from` (including `from` themselves) following `edges`.
+fn reachable(
+    edges: &[(usize, bool, usize)],
+    from: impl IntoIterator<Item = usize>,
+) -> HashSet<usize> {
+    let mut successors: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (f, _, t) in edges {
+        successors.entry(*f).or_default().push(*t);
+    }
+    let mut visited: HashSet<usize> = HashSet::new();
+    let mut stack: Vec<usize> = from.into_iter().collect();
+    while let Some(state) = stack.pop() {
+        if !visited.insert(state) {
+            continue;
+        }
+        if let Some(next) = successors.get(&state) {
+            stack.extend(next.iter().filter(|q| !visited.contains(q)));
+        }
+    }
+    visited
+}
+
+/// Whether there's a cycle containing at least one symbol-consuming edge in the subgraph of states that lie on
+/// some path from `start` to one of `accepting`.
+///
+/// Every strongly connected component of that subgraph is a bunch of states that can loop back to each other,
+/// so such a cycle exists iff a consuming edge connects two states of the same component.
+fn has_productive_cycle(
+    edges: &[(usize, bool, usize)],
+    start: usize,
+    accepting: &HashSet<usize>,
+) -> bool {
+    let forward = reachable(edges, [start]);
+    let reversed: Vec<(usize, bool, usize)> = edges.iter().map(|(f, c, t)| (*t, *c, *f)).collect();
+    let backward = reachable(&reversed, accepting.iter().cloned());
+    let useful: Vec<(usize, bool, usize)> = edges
+        .iter()
+        .filter(|(f, _, t)| {
+            forward.contains(f)
+                && backward.contains(f)
+                && forward.contains(t)
+                && backward.contains(t)
+        })
+        .cloned()
+        .collect();
+
+    let components = strongly_connected_components(&useful);
+    useful
+        .iter()
+        .any(|(f, consumes, t)| *consumes && components[f] == components[t])
+}
+
+/// Kosaraju's algorithm, returns a mapping of state to the id of its component.
+/// Only states occurring in `edges` are considered.
+fn strongly_connected_components(edges: &[(usize, bool, usize)]) -> HashMap<usize, usize> {
+    let mut successors: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut predecessors: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (f, _, t) in edges {
+        successors.entry(*f).or_default().push(*t);
+        predecessors.entry(*t).or_default().push(*f);
+        successors.entry(*t).or_default();
+        predecessors.entry(*f).or_default();
+    }
+
+    // first pass: order states by the time their dfs finishes
+    let mut finished: Vec<usize> = Vec::new();
+    let mut visited: HashSet<usize> = HashSet::new();
+    for root in successors.keys() {
+        if !visited.insert(*root) {
+            continue;
+        }
+        // iterative dfs, the index keeps track of which successor to visit next
+        let mut stack: Vec<(usize, usize)> = vec![(*root, 0)];
+        while let Some((state, i)) = stack.pop() {
+            if let Some(next) = successors[&state].get(i) {
+                stack.push((state, i + 1));
+                if visited.insert(*next) {
+                    stack.push((*next, 0));
+                }
+            } else {
+                finished.push(state);
+            }
+        }
+    }
+
+    // second pass: collect components on the reversed graph in reverse finishing order
+    let mut components: HashMap<usize, usize> = HashMap::new();
+    for (component, root) in finished.iter().rev().enumerate() {
+        if components.contains_key(root) {
+            continue;
+        }
+        let mut stack = vec![*root];
+        while let Some(state) = stack.pop() {
+            if components.contains_key(&state) {
+                continue;
+            }
+            components.insert(state, component);
+            stack.extend(
+                predecessors[&state]
+                    .iter()
+                    .filter(|q| !components.contains_key(q)),
+            );
+        }
+    }
+    components
+}
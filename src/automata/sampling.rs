@@ -0,0 +1,87 @@
+use crate::automata::Dfa;
+use rand_core::RngCore;
+use std::collections::HashMap;
+
+/////////////////////////////////////////////////////// SAMPLING ///////////////////////////////////////////////////////
+// Drawing a word of length `len` uniformly at random is a walk through the `Dfa` guided by the word counts
+// (see `counting.rs`): With `r` symbols left to choose in state `q`, the transition into `p` is taken with
+// probability `f_{r-1}(p) / f_r(q)`, i.e. proportional to the number of accepted words continuing through `p`.
+// Multiplying these up along the walk, every accepted word ends up with probability `1 / f_len(q_0)`.
+// Note that this is only exactly uniform as long as the counts don't saturate at `u128::MAX`.
+
+impl Dfa {
+    /// Draw a word of exactly `len` symbols uniformly at random from the language of `self`.
+    /// returns: `None` if `self` accepts no word of that length
+    pub fn sample(&self, len: usize, rng: &mut impl RngCore) -> Option<String> {
+        let table = self.count_table(len);
+        self._sample(&table, len, rng)
+    }
+
+    /// Draw a word of at most `max_len` symbols uniformly at random from the language of `self`.
+    /// Each length is chosen with a weight of its number of accepted words, so short lengths with only a handful
+    /// of words aren't overrepresented the way they would be when picking a length first.
+    /// returns: `None` if `self` accepts no word of at most `max_len` symbols
+    pub fn sample_up_to(&self, max_len: usize, rng: &mut impl RngCore) -> Option<String> {
+        let table = self.count_table(max_len);
+        let total = table
+            .iter()
+            .map(|counts| counts[&self.q_start])
+            .fold(0, u128::saturating_add);
+        if total == 0 {
+            return None;
+        }
+
+        let mut pick = uniform_below(rng, total);
+        for (len, counts) in table.iter().enumerate() {
+            let count = counts[&self.q_start];
+            if pick < count {
+                return self._sample(&table, len, rng);
+            }
+            pick -= count;
+        }
+        unreachable!("picked word is out of bounds")
+    }
+
+    /// Uniformly sample a word of length `len`, given the word counts `table` up to at least `len`.
+    fn _sample(
+        &self,
+        table: &[HashMap<usize, u128>],
+        len: usize,
+        rng: &mut impl RngCore,
+    ) -> Option<String> {
+        if table[len][&self.q_start] == 0 {
+            return None;
+        }
+
+        // sorted to stay reproducible for a seeded `rng`
+        let successors = self.sorted_successors();
+
+        let mut word = String::with_capacity(len);
+        let mut current = self.q_start;
+        for remaining in (0..len).rev() {
+            let mut pick = uniform_below(rng, table[remaining + 1][&current]);
+            for (c, to) in &successors[&current] {
+                let count = table[remaining][to];
+                if pick < count {
+                    word.push(*c);
+                    current = *to;
+                    break;
+                }
+                pick -= count;
+            }
+        }
+        Some(word)
+    }
+}
+
+/// Draw a number from `0..n` uniformly at random. `n` must not be zero.
+fn uniform_below(rng: &mut impl RngCore, n: u128) -> u128 {
+    // reject the lowest `2^128 mod n` numbers, as they would make the smaller remainders more likely
+    let threshold = n.wrapping_neg() % n;
+    loop {
+        let x = ((rng.next_u64() as u128) << 64) | rng.next_u64() as u128;
+        if x >= threshold {
+            return x % n;
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use crate::automata::binary::NO_TRANSITION;
+use crate::automata::{Automaton, Deterministic, find_leftmost_longest};
+
+/// A `Dfa` whose tables are compiled into the binary, as expanded by the `dfa!` macro of `rusty-dfa-macros` or
+/// generated by `Dfa::to_rust_static`. Being made of `&'static` slices only, it can live in a `static`.
+///
+/// States are dense indices, the alphabet is sorted so a symbol is looked up with a binary search,
+/// and `table` holds the transitions row by row with `u32::MAX` for a missing one, like the binary format.
+#[derive(Clone, Copy, Debug)]
+pub struct StaticDfa {
+    q_start: usize,
+    symbols: &'static [char],
+    accepting: &'static [bool],
+    table: &'static [u32],
+}
+
+impl StaticDfa {
+    /// Only meant for generated code, which guarantees the tables to be consistent.
+    #[doc(hidden)]
+    pub const fn from_parts(
+        q_start: usize,
+        symbols: &'static [char],
+        accepting: &'static [bool],
+        table: &'static [u32],
+    ) -> StaticDfa {
+        StaticDfa {
+            q_start,
+            symbols,
+            accepting,
+            table,
+        }
+    }
+
+    /// Whether all of `input` is accepted, same as `Automaton::accept`.
+    pub fn is_match(&self, input: &str) -> bool {
+        self.accept(input)
+    }
+
+    /// The leftmost longest non-empty match in `input` as inclusive `(start, end)` char indices, like `Nfa::find`.
+    pub fn find(&self, input: &str) -> Option<(usize, usize)> {
+        find_leftmost_longest(self, input)
+    }
+}
+
+impl Deterministic for StaticDfa {
+    fn start(&self) -> usize {
+        self.q_start
+    }
+
+    fn step(&self, state: usize, c: char) -> Option<usize> {
+        let j = self.symbols.binary_search(&c).ok()?;
+        match self.table[state * self.symbols.len() + j] {
+            NO_TRANSITION => None,
+            to => Some(to as usize),
+        }
+    }
+
+    fn is_accepting(&self, state: usize) -> bool {
+        self.accepting[state]
+    }
+}
+
+impl Automaton for StaticDfa {
+    /// Always valid, the tables are checked when they're generated.
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn accept(&self, input: &str) -> bool {
+        let mut current = self.start();
+        for c in input.chars() {
+            match self.step(current, c) {
+                Some(next) => current = next,
+                None => return false,
+            }
+        }
+        self.is_accepting(current)
+    }
+}
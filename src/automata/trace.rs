@@ -0,0 +1,110 @@
+use crate::automata::{Dfa, Nfa, Symbol};
+use std::collections::BTreeSet;
+
+////////////////////////////////////////////////////// TRACING ///////////////////////////////////////////////////////
+// Instead of the plain yes or no of `Automaton::accept`, a trace records the entire run on a word: every state (or set
+// of states) it went through and how it ended. For a rejected word, that's where and why it was rejected.
+
+/// The run of an automaton on a word, see `Dfa::trace` and `Nfa::trace`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trace<S> {
+    /// `steps[0]` is where the run starts and `steps[i]` where it is after reading `i` chars.
+    /// A run that died has no step for the char it died at, nor for any after it.
+    pub steps: Vec<S>,
+    pub outcome: Outcome,
+}
+
+/// How a run ended
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// All of the input was read and the run ended in an accepting state
+    Accepted,
+    /// All of the input was read, but the run ended in a state that isn't accepting
+    Rejected,
+    /// There was no transition for `symbol`, the char at index `position` of the input
+    Died { position: usize, symbol: char },
+}
+
+impl<S> Trace<S> {
+    pub fn is_accepted(&self) -> bool {
+        self.outcome == Outcome::Accepted
+    }
+
+    /// The step the run ended in, which is the one it died in if it did.
+    pub fn last(&self) -> &S {
+        // there always is the initial step
+        self.steps.last().unwrap()
+    }
+}
+
+/// A state of a `Dfa` visited by a run
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DfaStep {
+    pub state: usize,
+    /// The set of `Nfa` states `state` stands for, if the `Dfa` kept them, see `Dfa::from_with_provenance`
+    pub nfa_states: Option<BTreeSet<usize>>,
+}
+
+impl Dfa {
+    /// Run `self` on `input` like `Automaton::accept` does, but record every state along the way.
+    /// The steps only tell the `Nfa` states they stand for if `self` was built by `Dfa::from_with_provenance`.
+    pub fn trace(&self, input: &str) -> Trace<DfaStep> {
+        let step = |state: usize| DfaStep {
+            state,
+            nfa_states: self.nfa_states.get(&state).cloned(),
+        };
+        let mut current = self.q_start;
+        let mut steps = vec![step(current)];
+        for (position, c) in input.chars().enumerate() {
+            let Some(next) = self.transitions.get(&(current, Symbol::CHAR(c))) else {
+                let outcome = Outcome::Died {
+                    position,
+                    symbol: c,
+                };
+                return Trace { steps, outcome };
+            };
+            current = *next;
+            steps.push(step(current));
+        }
+
+        let outcome = if self.q_accepting.contains(&current) {
+            Outcome::Accepted
+        } else {
+            Outcome::Rejected
+        };
+        Trace { steps, outcome }
+    }
+}
+
+impl Nfa {
+    /// Simulate `self` on `input` and record the set of states it is in after every char.
+    /// Every set is closed under epsilon transitions, and the run dies as soon as the set is empty.
+    pub fn trace(&self, input: &str) -> Trace<BTreeSet<usize>> {
+        let successors = self.successors_single();
+        let mut current: BTreeSet<usize> = self.ec(self.q_start).into_iter().collect();
+        let mut steps = vec![current.clone()];
+        for (position, c) in input.chars().enumerate() {
+            current = current
+                .iter()
+                .filter_map(|q| successors.get(q)?.get(&Symbol::CHAR(c)))
+                .flatten()
+                .cloned()
+                .collect();
+            if current.is_empty() {
+                let outcome = Outcome::Died {
+                    position,
+                    symbol: c,
+                };
+                return Trace { steps, outcome };
+            }
+            steps.push(current.clone());
+        }
+
+        let outcome = if self.contains_accepting_state(&current) {
+            Outcome::Accepted
+        } else {
+            Outcome::Rejected
+        };
+        Trace { steps, outcome }
+    }
+}
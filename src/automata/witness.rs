@@ -0,0 +1,43 @@
+use crate::automata::{Dfa, Symbol};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+////////////////////////////////////////////////////// WITNESSES /////////////////////////////////////////////////////
+// Short example words proving that a `Dfa` does (or does not) accept something.
+
+impl Dfa {
+    /// The shortest word accepted by `self`, lexicographically smallest among words of the same length.
+    /// returns: `None` if the language of `self` is empty
+    pub fn shortest_accepted(&self) -> Option<String> {
+        // breadth first search, remembering how we reached every state to rebuild the word afterwards
+        let mut reached_by: HashMap<usize, (usize, char)> = HashMap::new();
+        let mut visited: HashSet<usize> = HashSet::from([self.q_start]);
+        let mut queue: VecDeque<usize> = VecDeque::from([self.q_start]);
+        let successors = self.sorted_successors();
+
+        while let Some(state) = queue.pop_front() {
+            if self.q_accepting.contains(&state) {
+                let mut word: Vec<char> = Vec::new();
+                let mut current = state;
+                while let Some((previous, c)) = reached_by.get(&current) {
+                    word.push(*c);
+                    current = *previous;
+                }
+                return Some(word.iter().rev().collect());
+            }
+            for (c, to) in successors.get(&state).into_iter().flatten() {
+                if visited.insert(*to) {
+                    reached_by.insert(*to, (state, *c));
+                    queue.push_back(*to);
+                }
+            }
+        }
+        None
+    }
+
+    /// The shortest word over `alphabet` (and the symbols `self` has transitions for) **not** accepted by `self`,
+    /// lexicographically smallest among words of the same length.
+    /// returns: `None` if `self` accepts every word over that alphabet
+    pub fn shortest_rejected(&self, alphabet: &HashSet<Symbol>) -> Option<String> {
+        self.complement(alphabet).shortest_accepted()
+    }
+}
@@ -1 +0,0 @@
-pub(crate) mod benchmark_dfa;
\ No newline at end of file
@@ -0,0 +1,22 @@
+use rusty_dfa::parse::ParseError;
+use std::ops::Range;
+
+pub mod grep;
+pub mod repl;
+
+/// Describe `error` along with the pattern and a marker below the offending part of it, over three lines.
+pub fn pattern_error(pattern: &str, error: &ParseError) -> String {
+    format!("invalid pattern: {}\n{}", error, mark(pattern, &error.span))
+}
+
+/// `pattern` with a marker below `span` on the line after it, both indented by two spaces
+pub fn mark(pattern: &str, span: &Range<usize>) -> String {
+    let indent = pattern[..span.start].chars().count();
+    let width = pattern[span.clone()].chars().count().max(1);
+    format!(
+        "  {}\n  {}{}",
+        pattern,
+        " ".repeat(indent),
+        "^".repeat(width)
+    )
+}
@@ -0,0 +1,329 @@
+use crate::cli::pattern_error;
+use clap::{Parser, ValueEnum};
+use rusty_dfa::matcher::Matcher;
+use rusty_dfa::parse::ParseError;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, IsTerminal, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+//////////////////////////////////////////////////////// GREP ////////////////////////////////////////////////////////
+// `rdfa PATTERN [FILES...]` prints every line containing a match of the pattern, just like grep does. Every line is
+// searched with a `Matcher`, i.e. the forward and the reverse dfa of the pattern, so the highlighted spans are exactly
+// the ones `Dfa::find_all` reports. As these are never empty, a pattern matching the empty word selects every line
+// without them, just like grep does, and `-o` prints nothing for such a line. The exit status is 0 if any line was
+// selected, 1 if none was and 2 if the pattern is invalid or an input couldn't be read.
+
+/// Search for PATTERN in each FILE, line by line
+#[derive(Parser)]
+#[command(name = "rdfa", version)]
+pub struct Args {
+    /// The pattern to search for
+    pattern: String,
+    /// Files to search, `-` being stdin. Without any, stdin is searched (or `.` with `-r`)
+    files: Vec<PathBuf>,
+    /// Only print the number of selected lines of each file
+    #[arg(short, long)]
+    count: bool,
+    /// Select the lines without a match instead
+    #[arg(short = 'v', long)]
+    invert_match: bool,
+    /// Only print the matches of a line, one per line
+    #[arg(short, long)]
+    only_matching: bool,
+    /// Prefix every line with its line number
+    #[arg(short = 'n', long)]
+    line_number: bool,
+    /// Match letters regardless of their case
+    #[arg(short, long)]
+    ignore_case: bool,
+    /// Search the files of directories recursively
+    #[arg(short, long)]
+    recursive: bool,
+    /// When to highlight matches, file names and line numbers
+    #[arg(long, value_enum, default_value_t = Color::Auto)]
+    color: Color,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Color {
+    /// Only if stdout is a terminal
+    Auto,
+    Always,
+    Never,
+}
+
+const MATCH: &str = "\x1b[1;31m";
+const FILE_NAME: &str = "\x1b[35m";
+const LINE_NUMBER: &str = "\x1b[32m";
+const SEPARATOR: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+enum Input {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Input {
+    fn name(&self) -> String {
+        match self {
+            Input::Stdin => String::from("(standard input)"),
+            Input::File(path) => path.display().to_string(),
+        }
+    }
+}
+
+pub fn run(args: &Args) -> ExitCode {
+    let matcher = match compile(args) {
+        Ok(matcher) => matcher,
+        Err(e) => {
+            eprintln!("rdfa: {}", pattern_error(&args.pattern, &e));
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut failed = false;
+    let inputs = inputs(args, &mut failed);
+    let with_name = args.recursive || args.files.len() > 1;
+    let color = match args.color {
+        Color::Auto => io::stdout().is_terminal(),
+        Color::Always => true,
+        Color::Never => false,
+    };
+    let mut printer = Printer {
+        out: BufWriter::new(io::stdout().lock()),
+        color,
+    };
+
+    let mut selected = false;
+    for input in inputs {
+        let reader: Box<dyn BufRead> = match &input {
+            Input::Stdin => Box::new(io::stdin().lock()),
+            Input::File(path) => match File::open(path) {
+                Ok(file) => Box::new(BufReader::new(file)),
+                Err(e) => {
+                    eprintln!("rdfa: {}: {}", path.display(), e);
+                    failed = true;
+                    continue;
+                }
+            },
+        };
+        let name = with_name.then(|| input.name());
+        match search(args, &matcher, reader, name.as_deref(), &mut printer) {
+            Ok(found) => selected |= found,
+            // whoever reads our output has seen enough, e.g. `rdfa a file | head`
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => return ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("rdfa: {}: {}", input.name(), e);
+                failed = true;
+            }
+        }
+    }
+    if let Err(e) = printer.out.flush()
+        && e.kind() != io::ErrorKind::BrokenPipe
+    {
+        eprintln!("rdfa: {}", e);
+        failed = true;
+    }
+
+    match (failed, selected) {
+        (true, _) => ExitCode::from(2),
+        (false, true) => ExitCode::SUCCESS,
+        (false, false) => ExitCode::from(1),
+    }
+}
+
+fn compile(args: &Args) -> Result<Matcher, ParseError> {
+    if !args.ignore_case {
+        return Matcher::new(&args.pattern);
+    }
+    // rewriting the pattern doesn't change whether it is valid, but the spans of an error should refer to the original
+    rusty_dfa::parse::try_parse(&args.pattern)?;
+    Matcher::new(&ignore_case(&args.pattern))
+}
+
+/// `pattern` with every letter `x` that has a different case `X` replaced by `(x|X)`.
+/// As there are neither classes nor escapes in the syntax, this is all it takes to ignore case.
+fn ignore_case(pattern: &str) -> String {
+    let single = |chars: &mut dyn Iterator<Item = char>| match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    };
+    pattern
+        .chars()
+        .map(|c| {
+            let lower = single(&mut c.to_lowercase());
+            let upper = single(&mut c.to_uppercase());
+            match (lower, upper) {
+                (Some(lower), Some(upper)) if lower != upper => format!("({}|{})", lower, upper),
+                _ => c.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Everything to search, in order. Problems with the given files are reported right away and set `failed`.
+fn inputs(args: &Args, failed: &mut bool) -> Vec<Input> {
+    let mut inputs = Vec::new();
+    if args.files.is_empty() {
+        if args.recursive {
+            walk(Path::new("."), &mut inputs, failed);
+        } else {
+            inputs.push(Input::Stdin);
+        }
+    }
+    for file in &args.files {
+        if file.as_os_str() == "-" {
+            inputs.push(Input::Stdin);
+        } else if !file.is_dir() {
+            inputs.push(Input::File(file.clone()));
+        } else if args.recursive {
+            walk(file, &mut inputs, failed);
+        } else {
+            eprintln!("rdfa: {}: Is a directory", file.display());
+            *failed = true;
+        }
+    }
+    inputs
+}
+
+/// Collect the regular files below `dir` in sorted order. Like grep, symlinks are not followed while descending.
+fn walk(dir: &Path, inputs: &mut Vec<Input>, failed: &mut bool) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("rdfa: {}: {}", dir.display(), e);
+            *failed = true;
+            return;
+        }
+    };
+    let mut entries: Vec<_> = entries.filter_map(Result::ok).collect();
+    entries.sort_by_key(|entry| entry.path());
+    for entry in entries {
+        let path = entry.path();
+        // `rdfa -r a` should print `file`, not `./file`
+        let path = path
+            .strip_prefix(".")
+            .map(Path::to_path_buf)
+            .unwrap_or(path);
+        match entry.file_type() {
+            Ok(kind) if kind.is_dir() => walk(&path, inputs, failed),
+            Ok(kind) if kind.is_file() => inputs.push(Input::File(path)),
+            _ => {}
+        }
+    }
+}
+
+/// Search the lines of `reader` and print whatever `args` asks for, prefixed with `name` if given.
+///
+/// returns: Whether a line was selected
+fn search(
+    args: &Args,
+    matcher: &Matcher,
+    mut reader: impl BufRead,
+    name: Option<&str>,
+    printer: &mut Printer<impl Write>,
+) -> io::Result<bool> {
+    // the empty word matches at every position of every line
+    let everywhere = matcher.is_match("");
+    let mut selected = 0;
+    let mut buffer = Vec::new();
+    let mut number = 0;
+    loop {
+        buffer.clear();
+        if reader.read_until(b'\n', &mut buffer)? == 0 {
+            break;
+        }
+        number += 1;
+        if buffer.last() == Some(&b'\n') {
+            buffer.pop();
+        }
+        let line = String::from_utf8_lossy(&buffer);
+        let matches = matcher.find_all(&line);
+        if (matches.is_empty() && !everywhere) != args.invert_match {
+            continue;
+        }
+        selected += 1;
+        if args.count {
+            continue;
+        }
+
+        let number = args.line_number.then_some(number);
+        let spans = byte_spans(&line, &matches);
+        if args.only_matching {
+            // an inverted line has no matches to print
+            for span in spans {
+                printer.prefix(name, number)?;
+                printer.paint(MATCH, &line[span])?;
+                writeln!(printer.out)?;
+            }
+        } else {
+            printer.prefix(name, number)?;
+            printer.line(&line, &spans)?;
+        }
+    }
+
+    if args.count {
+        printer.prefix(name, None)?;
+        writeln!(printer.out, "{}", selected)?;
+    }
+    Ok(selected > 0)
+}
+
+/// Convert the inclusive char spans of `Matcher::find_all` into byte ranges of `line`.
+/// Overlapping spans are dropped, so the ranges can be highlighted one after another.
+fn byte_spans(line: &str, matches: &[(usize, usize)]) -> Vec<Range<usize>> {
+    let offsets: Vec<usize> = line
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain([line.len()])
+        .collect();
+    let mut spans: Vec<Range<usize>> = Vec::new();
+    for (start, end) in matches {
+        let span = offsets[*start]..offsets[end + 1];
+        if spans.last().is_none_or(|last| last.end <= span.start) {
+            spans.push(span);
+        }
+    }
+    spans
+}
+
+struct Printer<W: Write> {
+    out: W,
+    color: bool,
+}
+
+impl<W: Write> Printer<W> {
+    fn paint(&mut self, color: &str, text: &str) -> io::Result<()> {
+        if self.color {
+            write!(self.out, "{}{}{}", color, text, RESET)
+        } else {
+            write!(self.out, "{}", text)
+        }
+    }
+
+    /// `name:` and `number:` in front of a line, as far as they are given
+    fn prefix(&mut self, name: Option<&str>, number: Option<usize>) -> io::Result<()> {
+        if let Some(name) = name {
+            self.paint(FILE_NAME, name)?;
+            self.paint(SEPARATOR, ":")?;
+        }
+        if let Some(number) = number {
+            self.paint(LINE_NUMBER, &number.to_string())?;
+            self.paint(SEPARATOR, ":")?;
+        }
+        Ok(())
+    }
+
+    /// `line` with the byte ranges `spans` highlighted
+    fn line(&mut self, line: &str, spans: &[Range<usize>]) -> io::Result<()> {
+        let mut last = 0;
+        for span in spans {
+            write!(self.out, "{}", &line[last..span.start])?;
+            self.paint(MATCH, &line[span.clone()])?;
+            last = span.end;
+        }
+        writeln!(self.out, "{}", &line[last..])
+    }
+}
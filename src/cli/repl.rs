@@ -0,0 +1,262 @@
+use crate::cli::{mark, pattern_error};
+use rusty_dfa::automata::ambiguity::Ambiguity;
+use rusty_dfa::automata::{Automaton, Dfa, Nfa};
+use rusty_dfa::lint::lint;
+use rusty_dfa::parse::try_parse;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::process::ExitCode;
+
+//////////////////////////////////////////////////////// REPL ////////////////////////////////////////////////////////
+// `rdfa repl` reads one line at a time: the first one is the pattern, every following one a word to test against it,
+// and lines starting with `:` are commands to inspect the automata of the pattern, see `HELP`.
+// Whatever the input, the session goes on. Invalid patterns and commands are reported, and should one of the
+// algorithms panic anyway, the panic is caught and reported just like any other error.
+
+const HELP: &str = "\
+PATTERN             set the pattern, if there is none yet
+WORD                test whether WORD is accepted by the pattern
+\"WORD\"              test WORD verbatim, e.g. \"\" for the empty word or \":a\"
+:pattern PATTERN    set a new pattern (short :p)
+:nfa                print the Nfa built by the parser
+:dfa                print the Dfa of the powerset construction
+:min                print the minimal Dfa
+:dot [nfa|dfa|min]  print an automaton in the DOT language, the Dfa by default
+:examples [N]       print the first N (default 10) accepted words, shortest first
+:equiv PATTERN      check whether PATTERN accepts the same words as the pattern
+:lint               point out the parts of the pattern that are most likely mistakes
+:ambiguity          check whether the pattern is safe to use with a backtracking engine
+:help               print this
+:quit               end the session (short :q, or end of input)";
+
+/// A pattern along with all of its automata
+struct Compiled {
+    pattern: String,
+    nfa: Nfa,
+    dfa: Dfa,
+    min: Dfa,
+}
+
+impl Compiled {
+    fn new(pattern: &str) -> Result<Compiled, String> {
+        let nfa = try_parse(pattern).map_err(|e| pattern_error(pattern, &e))?;
+        let dfa = Dfa::from(&nfa);
+        let min = dfa.minimize();
+        Ok(Compiled {
+            pattern: String::from(pattern),
+            nfa,
+            dfa,
+            min,
+        })
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "Nfa with {} states, Dfa with {} states, {} of which are needed",
+            self.nfa.states.len(),
+            self.dfa.states().len(),
+            self.min.states().len()
+        )
+    }
+}
+
+#[derive(Default)]
+struct Session {
+    current: Option<Compiled>,
+}
+
+pub fn run() -> ExitCode {
+    let interactive = io::stdin().is_terminal();
+    if interactive {
+        println!("Enter a pattern, then words to test against it. :help lists all commands.");
+    }
+    // panics are reported as the error of the line that caused them instead
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut session = Session::default();
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        if interactive {
+            print!("rdfa> ");
+            let _ = io::stdout().flush();
+        }
+        let line = match lines.next() {
+            None => break,
+            Some(Ok(line)) => line,
+            // the offending line has been consumed, so it's fine to carry on
+            Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData => {
+                println!("error: input is not UTF-8");
+                continue;
+            }
+            Some(Err(e)) => {
+                eprintln!("rdfa: {}", e);
+                return ExitCode::from(2);
+            }
+        };
+        let line = line.trim_end_matches('\r');
+        if matches!(line.trim(), ":q" | ":quit") {
+            break;
+        }
+
+        let reply =
+            panic::catch_unwind(AssertUnwindSafe(|| session.eval(line))).unwrap_or_else(|panic| {
+                let reason = panic
+                    .downcast_ref::<&str>()
+                    .map(|s| s.to_string())
+                    .or_else(|| panic.downcast_ref::<String>().cloned())
+                    .unwrap_or_default();
+                Err(format!("internal error: {}", reason))
+            });
+        match reply {
+            Ok(text) if text.is_empty() => {}
+            Ok(text) => println!("{}", text),
+            Err(e) => println!("error: {}", e),
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+impl Session {
+    /// Evaluate a single line of input, see `HELP`.
+    ///
+    /// returns: What to print, nothing if empty
+    fn eval(&mut self, line: &str) -> Result<String, String> {
+        let Some(command) = line.strip_prefix(':') else {
+            return self.input(line);
+        };
+        let (name, argument) = match command.split_once(char::is_whitespace) {
+            Some((name, argument)) => (name, argument.trim_start()),
+            None => (command, ""),
+        };
+
+        match name {
+            "help" | "h" => Ok(String::from(HELP)),
+            "pattern" | "p" => {
+                let compiled = Compiled::new(argument)?;
+                let summary = compiled.summary();
+                self.current = Some(compiled);
+                Ok(summary)
+            }
+            "nfa" => Ok(format!("{:?}", self.compiled()?.nfa)),
+            "dfa" => Ok(self.compiled()?.dfa.to_string()),
+            "min" => Ok(self.compiled()?.min.to_string()),
+            "dot" => {
+                let compiled = self.compiled()?;
+                match argument.trim() {
+                    "nfa" => Ok(compiled.nfa.to_dot_with_pattern(&compiled.pattern)),
+                    "" | "dfa" => Ok(compiled.dfa.to_dot()),
+                    "min" => Ok(compiled.min.to_dot()),
+                    other => Err(format!(
+                        "Unknown automaton \"{}\", expected nfa, dfa or min",
+                        other
+                    )),
+                }
+            }
+            "examples" => {
+                let n = match argument.trim() {
+                    "" => 10,
+                    n => n
+                        .parse::<usize>()
+                        .map_err(|_| format!("\"{}\" is not a number of examples", n))?,
+                };
+                let words: Vec<String> = self
+                    .compiled()?
+                    .min
+                    .words(None)
+                    .take(n)
+                    .map(|word| {
+                        if word.is_empty() {
+                            String::from("\"\"")
+                        } else {
+                            word
+                        }
+                    })
+                    .collect();
+                if words.is_empty() && n > 0 {
+                    Ok(String::from("No word is accepted"))
+                } else {
+                    Ok(words.join("\n"))
+                }
+            }
+            "equiv" => {
+                let compiled = self.compiled()?;
+                let other =
+                    Dfa::from(&try_parse(argument).map_err(|e| pattern_error(argument, &e))?);
+                // the shortest word accepted by exactly one of both is the best counterexample there is
+                match compiled
+                    .dfa
+                    .symmetric_difference(&other)
+                    .shortest_accepted()
+                {
+                    None => Ok(String::from("equivalent")),
+                    Some(word) => Ok(format!(
+                        "not equivalent, \"{}\" is only accepted by {}",
+                        word,
+                        if compiled.dfa.accept(&word) {
+                            "the pattern"
+                        } else {
+                            argument
+                        }
+                    )),
+                }
+            }
+            "lint" => {
+                let pattern = &self.compiled()?.pattern;
+                // the pattern has been parsed before, so it's valid
+                let lints = lint(pattern).unwrap();
+                if lints.is_empty() {
+                    return Ok(String::from("Nothing to complain about"));
+                }
+                let warnings: Vec<String> = lints
+                    .iter()
+                    .map(|lint| format!("warning: {}\n{}", lint.message, mark(pattern, &lint.span)))
+                    .collect();
+                Ok(warnings.join("\n"))
+            }
+            "ambiguity" => Ok(match self.compiled()?.nfa.ambiguity() {
+                Ambiguity::Bounded => String::from("bounded, safe for backtracking engines"),
+                Ambiguity::Polynomial { degree, attack } => format!(
+                    "polynomial of degree {}, words like {} take a backtracking engine long to reject",
+                    degree, attack
+                ),
+                Ambiguity::Exponential { attack } => format!(
+                    "exponential, words like {} take a backtracking engine forever to reject",
+                    attack
+                ),
+            }),
+            _ => Err(format!(
+                "Unknown command \":{}\", :help lists all commands",
+                name
+            )),
+        }
+    }
+
+    /// Anything that isn't a command: the pattern if there is none yet, a word to test otherwise
+    fn input(&mut self, line: &str) -> Result<String, String> {
+        let Some(compiled) = &self.current else {
+            if line.is_empty() {
+                return Ok(String::new());
+            }
+            let compiled = Compiled::new(line)?;
+            let summary = compiled.summary();
+            self.current = Some(compiled);
+            return Ok(summary);
+        };
+        let word = match line.strip_prefix('"').and_then(|l| l.strip_suffix('"')) {
+            Some(word) => word,
+            None if line.is_empty() => return Ok(String::new()),
+            None => line,
+        };
+        Ok(String::from(if compiled.dfa.accept(word) {
+            "accepted"
+        } else {
+            "rejected"
+        }))
+    }
+
+    fn compiled(&self) -> Result<&Compiled, String> {
+        self.current
+            .as_ref()
+            .ok_or_else(|| String::from("There is no pattern yet, enter one first"))
+    }
+}
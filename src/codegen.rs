@@ -0,0 +1,80 @@
+use crate::automata::{Dfa, Symbol};
+use std::collections::HashMap;
+
+mod c;
+pub mod rust;
+
+/// A `Dfa` renumbered to `0..n` with its alphabet in ascending order, which is what generated code works with.
+pub(crate) struct Dense {
+    pub q_start: usize,
+    pub accepting: Vec<bool>,
+    pub symbols: Vec<char>,
+    /// `table[state][j]` is the target of `state` with `symbols[j]`
+    pub table: Vec<Vec<Option<usize>>>,
+}
+
+impl From<&Dfa> for Dense {
+    fn from(dfa: &Dfa) -> Dense {
+        let mut states = dfa.states().clone();
+        states.sort();
+        let index: HashMap<usize, usize> =
+            states.iter().enumerate().map(|(i, q)| (*q, i)).collect();
+        // a valid dfa has nothing but chars in its alphabet
+        let mut symbols: Vec<char> = dfa
+            .alphabet()
+            .iter()
+            .filter_map(|s| match s {
+                Symbol::CHAR(c) => Some(*c),
+                _ => None,
+            })
+            .collect();
+        symbols.sort();
+
+        let table = states
+            .iter()
+            .map(|q| {
+                symbols
+                    .iter()
+                    .map(|c| {
+                        dfa.transitions()
+                            .get(&(*q, Symbol::CHAR(*c)))
+                            .map(|to| index[to])
+                    })
+                    .collect()
+            })
+            .collect();
+        Dense {
+            q_start: index[&dfa.q_start],
+            accepting: states
+                .iter()
+                .map(|q| dfa.q_accepting().contains(q))
+                .collect(),
+            symbols,
+            table,
+        }
+    }
+}
+
+impl Dense {
+    /// The transitions of `state` as inclusive char ranges sharing the same target, e.g. `('a', 'f', 1)`.
+    /// Only chars that are actually consecutive code points are merged.
+    pub fn ranges(&self, state: usize) -> Vec<(char, char, usize)> {
+        let mut ranges: Vec<(char, char, usize)> = Vec::new();
+        for (c, target) in self.symbols.iter().zip(&self.table[state]) {
+            let Some(target) = target else { continue };
+            match ranges.last_mut() {
+                Some((_, end, to)) if to == target && (*end as u32) + 1 == *c as u32 => *end = *c,
+                _ => ranges.push((*c, *c, *target)),
+            }
+        }
+        ranges
+    }
+}
+
+/// Whether `name` can be used as identifier in generated code, which is the common subset of Rust and C
+pub(crate) fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && name != "_"
+}
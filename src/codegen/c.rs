@@ -0,0 +1,201 @@
+use crate::automata::Dfa;
+use crate::codegen::{Dense, is_identifier};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/////////////////////////////////////////////////////// C CODE ///////////////////////////////////////////////////////
+// Generated matchers are a C99 header/source pair without any dependencies beyond the standard headers. For `name`:
+//
+// - `int name_match(const unsigned char *input, size_t len)`, 1 if all of `input` is accepted, 0 otherwise
+// - `int name_find(const unsigned char *input, size_t len, size_t *start, size_t *end)`, 1 if there is a match and
+//   `input[*start..*end)` is the leftmost longest non-empty one, 0 otherwise
+//
+// Just like `.resources/dfa.c`, the state machine runs on bytes with a table of 256 successors per state, only that
+// the states are precomputed instead of being built on the fly. Input is expected to be UTF-8: every symbol that
+// isn't ASCII becomes a chain of one transition per byte, so all offsets reported by `find` are byte offsets.
+// State 0 is the dead state, which every missing transition leads to.
+
+impl Dfa {
+    /// Generate a C99 header and source file implementing a matcher `name` for the language of `self`,
+    /// see `c.rs`. The source includes the header as `"name.h"`.
+    ///
+    /// returns: `(header, source)`
+    ///
+    /// # Panics
+    /// If `name` isn't a valid C identifier
+    pub fn to_c(&self, name: &str) -> (String, String) {
+        assert!(
+            is_identifier(name),
+            "Requested generation of C code with invalid name \"{}\"",
+            name
+        );
+        let bytes = ByteDfa::from(&Dense::from(self));
+        (header(name), source(name, &bytes))
+    }
+}
+
+/// A `Dense` dfa running on the UTF-8 encoding of its symbols, with the dead state 0 prepended
+struct ByteDfa {
+    q_start: usize,
+    accepting: Vec<bool>,
+    table: Vec<[usize; 256]>,
+}
+
+impl From<&Dense> for ByteDfa {
+    fn from(dense: &Dense) -> ByteDfa {
+        let states = dense.accepting.len();
+        let mut accepting = vec![false];
+        accepting.extend(&dense.accepting);
+        let mut table = vec![[0; 256]; states + 1];
+
+        // intermediate states of multi-byte symbols, shared between all symbols with the same prefix
+        let mut intermediate: HashMap<(usize, u8), usize> = HashMap::new();
+        for state in 0..states {
+            for (c, target) in dense.symbols.iter().zip(&dense.table[state]) {
+                let Some(target) = target else { continue };
+                let mut buffer = [0; 4];
+                let encoded = c.encode_utf8(&mut buffer).as_bytes();
+                let (last, prefix) = encoded.split_last().unwrap();
+                let mut current = state + 1;
+                for byte in prefix {
+                    current = *intermediate.entry((current, *byte)).or_insert_with(|| {
+                        accepting.push(false);
+                        table.push([0; 256]);
+                        table[current][*byte as usize] = table.len() - 1;
+                        table.len() - 1
+                    });
+                }
+                table[current][*last as usize] = target + 1;
+            }
+        }
+
+        ByteDfa {
+            q_start: dense.q_start + 1,
+            accepting,
+            table,
+        }
+    }
+}
+
+fn header(name: &str) -> String {
+    let guard = format!("{}_H", name.to_uppercase());
+    format!(
+        "/* Generated by rusty-dfa, do not edit. */
+#ifndef {guard}
+#define {guard}
+
+#include <stddef.h>
+
+/* 1 if all of input[0..len) is accepted, 0 otherwise. */
+int {name}_match(const unsigned char *input, size_t len);
+
+/*
+ * Find the leftmost longest non-empty match in input[0..len).
+ * Returns 1 and stores the match as input[*start..*end) if there is one, 0 otherwise.
+ */
+int {name}_find(const unsigned char *input, size_t len, size_t *start, size_t *end);
+
+#endif
+"
+    )
+}
+
+fn source(name: &str, dfa: &ByteDfa) -> String {
+    let states = dfa.table.len();
+    let state_type = if states <= 1 << 8 {
+        "uint8_t"
+    } else if states <= 1 << 16 {
+        "uint16_t"
+    } else {
+        "uint32_t"
+    };
+
+    let mut code = String::new();
+    writeln!(code, "/* Generated by rusty-dfa, do not edit. */").unwrap();
+    writeln!(code, "#include \"{}.h\"\n#include <stdint.h>\n", name).unwrap();
+    writeln!(code, "typedef {} state_t;\n", state_type).unwrap();
+    writeln!(
+        code,
+        "/* 0 is the dead state */\n#define START {}\n",
+        dfa.q_start
+    )
+    .unwrap();
+
+    let accepting: Vec<&str> = dfa
+        .accepting
+        .iter()
+        .map(|a| if *a { "1" } else { "0" })
+        .collect();
+    writeln!(
+        code,
+        "static const unsigned char accepting[{}] = {{{}}};\n",
+        states,
+        accepting.join(", ")
+    )
+    .unwrap();
+
+    writeln!(
+        code,
+        "static const state_t transitions[{}][256] = {{",
+        states
+    )
+    .unwrap();
+    for row in &dfa.table {
+        let cells: Vec<String> = row
+            .iter()
+            .enumerate()
+            .filter(|(_, to)| **to != 0)
+            .map(|(byte, to)| format!("[{:#04x}] = {}", byte, to))
+            .collect();
+        if cells.is_empty() {
+            code.push_str("    {0},\n");
+        } else {
+            writeln!(code, "    {{{}}},", cells.join(", ")).unwrap();
+        }
+    }
+    code.push_str("};\n");
+
+    write!(
+        code,
+        "
+int {name}_match(const unsigned char *input, size_t len)
+{{
+    size_t i;
+    state_t state = START;
+
+    for (i = 0; i < len; i++) {{
+        state = transitions[state][input[i]];
+        if (state == 0)
+            return 0;
+    }}
+    return accepting[state];
+}}
+
+int {name}_find(const unsigned char *input, size_t len, size_t *start, size_t *end)
+{{
+    size_t from, i;
+
+    for (from = 0; from < len; from++) {{
+        state_t state = START;
+        int found = 0;
+        for (i = from; i < len; i++) {{
+            state = transitions[state][input[i]];
+            if (state == 0)
+                break;
+            if (accepting[state]) {{
+                *end = i + 1;
+                found = 1;
+            }}
+        }}
+        if (found) {{
+            *start = from;
+            return 1;
+        }}
+    }}
+    return 0;
+}}
+"
+    )
+    .unwrap();
+    code
+}
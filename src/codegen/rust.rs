@@ -0,0 +1,221 @@
+use crate::automata::Dfa;
+use crate::codegen::{Dense, is_identifier};
+use std::fmt::Write;
+
+////////////////////////////////////////////////////// RUST CODE //////////////////////////////////////////////////////
+// Generated matchers are a single module without any dependencies, e.g. to be written by a `build.rs` and pulled in
+// with `include!(concat!(env!("OUT_DIR"), "/matcher.rs"))`. Every module exposes
+//
+// - `pub fn is_match(input: &str) -> bool`, whether all of `input` is accepted, just like `Automaton::accept`
+// - `pub fn find(input: &str) -> Option<(usize, usize)>`, the leftmost longest non-empty match as inclusive
+//   `(start, end)` char indices, just like `Nfa::find`
+//
+// No reversed automaton is needed for `find`, every possible start is tried instead. That's quadratic in the worst
+// case, but keeps the generated code down to the one state machine.
+//
+// `Dfa::to_rust_static` instead generates a single expression evaluating to a `StaticDfa`, which is what the `dfa!`
+// macro expands to. That one needs this crate at runtime, but can be used in a `static`.
+
+const KEYWORDS: [&str; 39] = [
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+    "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while",
+];
+
+/// How the transition function of generated Rust code looks like
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RustStyle {
+    /// A `match` over states and chars, which the compiler can turn into jump tables and range checks
+    Match,
+    /// A static transition table indexed by a binary search over the alphabet, smaller for large automata
+    Table,
+}
+
+impl Dfa {
+    /// Generate a standalone Rust module `name` matching the language of `self`, see `rust.rs`.
+    ///
+    /// # Panics
+    /// If `name` isn't a valid identifier for a module
+    pub fn to_rust(&self, name: &str, style: RustStyle) -> String {
+        assert!(
+            is_identifier(name) && !KEYWORDS.contains(&name),
+            "Requested generation of Rust code with invalid module name \"{}\"",
+            name
+        );
+        let dense = Dense::from(self);
+
+        let mut code = String::new();
+        writeln!(code, "// Generated by rusty-dfa, do not edit.").unwrap();
+        writeln!(
+            code,
+            "// {} states, {} symbols",
+            dense.accepting.len(),
+            dense.symbols.len()
+        )
+        .unwrap();
+        writeln!(code, "#[allow(dead_code, clippy::all)]").unwrap();
+        writeln!(code, "pub mod {} {{", name).unwrap();
+        writeln!(code, "    const START: usize = {};\n", dense.q_start).unwrap();
+        match style {
+            RustStyle::Match => write_match(&mut code, &dense),
+            RustStyle::Table => write_table(&mut code, &dense),
+        }
+        code.push_str(RUNS);
+        code.push_str("}\n");
+        code
+    }
+
+    /// Generate a constant Rust expression constructing a `StaticDfa` that matches the language of `self`.
+    pub fn to_rust_static(&self) -> String {
+        let dense = Dense::from(self);
+        let symbols: Vec<String> = dense.symbols.iter().map(|c| format!("{:?}", c)).collect();
+        let accepting: Vec<String> = dense.accepting.iter().map(|a| a.to_string()).collect();
+        let table: Vec<String> = dense
+            .table
+            .iter()
+            .flatten()
+            .map(|to| match to {
+                Some(to) => to.to_string(),
+                None => String::from("u32::MAX"),
+            })
+            .collect();
+        format!(
+            "::rusty_dfa::automata::static_dfa::StaticDfa::from_parts({}, &[{}], &[{}], &[{}])",
+            dense.q_start,
+            symbols.join(", "),
+            accepting.join(", "),
+            table.join(", ")
+        )
+    }
+}
+
+fn write_match(code: &mut String, dense: &Dense) {
+    code.push_str("    #[inline]\n    fn step(state: usize, c: char) -> Option<usize> {\n        match state {\n");
+    for state in 0..dense.table.len() {
+        let ranges = dense.ranges(state);
+        if ranges.is_empty() {
+            continue;
+        }
+        writeln!(code, "            {} => match c {{", state).unwrap();
+        for (start, end, to) in ranges {
+            if start == end {
+                writeln!(code, "                {:?} => Some({}),", start, to).unwrap();
+            } else {
+                writeln!(
+                    code,
+                    "                {:?}..={:?} => Some({}),",
+                    start, end, to
+                )
+                .unwrap();
+            }
+        }
+        code.push_str("                _ => None,\n            },\n");
+    }
+    code.push_str("            _ => None,\n        }\n    }\n\n");
+
+    let accepting: Vec<String> = (0..dense.accepting.len())
+        .filter(|q| dense.accepting[*q])
+        .map(|q| q.to_string())
+        .collect();
+    code.push_str("    #[inline]\n    fn is_accepting(state: usize) -> bool {\n");
+    if accepting.is_empty() {
+        code.push_str("        let _ = state;\n        false\n");
+    } else {
+        writeln!(code, "        matches!(state, {})", accepting.join(" | ")).unwrap();
+    }
+    code.push_str("    }\n");
+}
+
+fn write_table(code: &mut String, dense: &Dense) {
+    let (states, symbols) = (dense.accepting.len(), dense.symbols.len());
+    code.push_str("    const NONE: u32 = u32::MAX;\n");
+    let chars: Vec<String> = dense.symbols.iter().map(|c| format!("{:?}", c)).collect();
+    writeln!(
+        code,
+        "    static SYMBOLS: [char; {}] = [{}];",
+        symbols,
+        chars.join(", ")
+    )
+    .unwrap();
+    let accepting: Vec<String> = dense.accepting.iter().map(|a| a.to_string()).collect();
+    writeln!(
+        code,
+        "    static ACCEPTING: [bool; {}] = [{}];",
+        states,
+        accepting.join(", ")
+    )
+    .unwrap();
+    writeln!(
+        code,
+        "    static TABLE: [[u32; {}]; {}] = [",
+        symbols, states
+    )
+    .unwrap();
+    for row in &dense.table {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|to| match to {
+                Some(to) => to.to_string(),
+                None => String::from("NONE"),
+            })
+            .collect();
+        writeln!(code, "        [{}],", cells.join(", ")).unwrap();
+    }
+    code.push_str("    ];\n\n");
+    code.push_str(
+        "    #[inline]
+    fn step(state: usize, c: char) -> Option<usize> {
+        let j = SYMBOLS.binary_search(&c).ok()?;
+        match TABLE[state][j] {
+            NONE => None,
+            to => Some(to as usize),
+        }
+    }
+
+    #[inline]
+    fn is_accepting(state: usize) -> bool {
+        ACCEPTING[state]
+    }
+",
+    );
+}
+
+/// Everything built on top of `step` and `is_accepting`, which is the same for every style
+const RUNS: &str = "
+    /// Whether all of `input` is accepted
+    pub fn is_match(input: &str) -> bool {
+        let mut state = START;
+        for c in input.chars() {
+            match step(state, c) {
+                Some(next) => state = next,
+                None => return false,
+            }
+        }
+        is_accepting(state)
+    }
+
+    /// The leftmost longest non-empty match in `input` as inclusive `(start, end)` char indices
+    pub fn find(input: &str) -> Option<(usize, usize)> {
+        let mut rest = input.chars();
+        let mut start = 0;
+        loop {
+            let mut state = START;
+            let mut end = None;
+            for (pos, c) in rest.clone().enumerate() {
+                match step(state, c) {
+                    Some(next) => state = next,
+                    None => break,
+                }
+                if is_accepting(state) {
+                    end = Some(start + pos);
+                }
+            }
+            if let Some(end) = end {
+                return Some((start, end));
+            }
+            rest.next()?;
+            start += 1;
+        }
+    }
+";
@@ -0,0 +1,33 @@
+use crate::automata::{Automaton, Dfa, Symbol};
+use std::collections::{HashMap, HashSet};
+
+mod jflap;
+mod json;
+
+/// Build a `Dfa` from the parts of an imported automaton.
+/// returns: Err(reason) if there are epsilon transitions, more than one transition per state and symbol or the
+/// result is invalid otherwise
+fn dfa_from_parts(
+    states: Vec<usize>,
+    transitions: Vec<(usize, Symbol, usize)>,
+    q_start: usize,
+    q_accepting: HashSet<usize>,
+) -> Result<Dfa, String> {
+    let mut dfa_transitions: HashMap<(usize, Symbol), usize> = HashMap::new();
+    for (from, with, to) in transitions {
+        if with == Symbol::EPSILON {
+            return Err(String::from("A Dfa can't have epsilon transitions"));
+        }
+        if let Some(other) = dfa_transitions.insert((from, with), to)
+            && other != to
+        {
+            return Err(format!(
+                "State {} has more than one transition with {:?}",
+                from, with
+            ));
+        }
+    }
+    let dfa = Dfa::new(states, dfa_transitions, q_start, q_accepting);
+    dfa.validate()?;
+    Ok(dfa)
+}
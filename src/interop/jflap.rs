@@ -0,0 +1,256 @@
+use crate::automata::{Dfa, Nfa, Symbol, next_state};
+use crate::interop::dfa_from_parts;
+use roxmltree::{Document, Node};
+use std::collections::{HashMap, HashSet};
+
+//////////////////////////////////////////////////////// JFLAP ////////////////////////////////////////////////////////
+// JFLAP stores finite automata as XML (`.jff`):
+//
+// <structure>
+//     <type>fa</type>
+//     <automaton>
+//         <state id="0" name="q0"><x>100.0</x><y>100.0</y><initial/></state>
+//         <state id="1" name="q1"><x>250.0</x><y>100.0</y><final/></state>
+//         <transition><from>0</from><to>1</to><read>a</read></transition>
+//         <transition><from>1</from><to>1</to><read/></transition>
+//     </automaton>
+// </structure>
+//
+// - An empty `<read/>` is JFLAP's empty string, i.e. `Symbol::EPSILON`.
+// - A `<read>` of several characters is a shorthand of JFLAP for a chain of transitions. It's expanded into fresh
+//   intermediate states for an `Nfa` and rejected for a `Dfa`.
+// - Older versions of JFLAP put states and transitions directly into `<structure>`, which is read just as well.
+// - Coordinates and names are ignored on import. On export, states are named after their id and laid out on a grid,
+//   as JFLAP stacks all states without coordinates on top of each other.
+
+/// States per row when laying out an exported automaton
+const COLUMNS: usize = 6;
+const SPACING: usize = 150;
+
+impl Nfa {
+    /// Export `self` as JFLAP finite automaton, see `jflap.rs`.
+    pub fn to_jff(&self) -> String {
+        to_jff(
+            &self.states,
+            self.q_start,
+            &self.q_accepting,
+            self.transitions.iter().cloned(),
+        )
+    }
+
+    /// Import an `Nfa` from a JFLAP finite automaton, see `jflap.rs`.
+    /// returns: Err(reason) if `xml` isn't a finite automaton of JFLAP or describes an invalid automaton
+    pub fn from_jff(xml: &str) -> Result<Nfa, String> {
+        let (mut states, transitions, q_start, q_accepting) = from_jff(xml)?;
+        let mut nfa_transitions: HashSet<(usize, Symbol, usize)> = HashSet::new();
+        for (from, read, to) in transitions {
+            let chars: Vec<char> = read.chars().collect();
+            if chars.is_empty() {
+                nfa_transitions.insert((from, Symbol::EPSILON, to));
+                continue;
+            }
+            // ab is a -> (fresh state) -> b
+            let mut current = from;
+            for (i, c) in chars.iter().enumerate() {
+                let next = if i == chars.len() - 1 {
+                    to
+                } else {
+                    let state = next_state();
+                    states.push(state);
+                    state
+                };
+                nfa_transitions.insert((current, Symbol::CHAR(*c), next));
+                current = next;
+            }
+        }
+        Nfa::try_new(states, nfa_transitions, q_start, q_accepting)
+    }
+}
+
+impl Dfa {
+    /// Export `self` as JFLAP finite automaton, see `jflap.rs`.
+    pub fn to_jff(&self) -> String {
+        to_jff(
+            self.states(),
+            self.q_start,
+            self.q_accepting(),
+            self.transitions()
+                .iter()
+                .map(|((from, with), to)| (*from, *with, *to)),
+        )
+    }
+
+    /// Import a `Dfa` from a JFLAP finite automaton, see `jflap.rs`.
+    /// returns: Err(reason) if `xml` isn't a finite automaton of JFLAP or describes an invalid or non-deterministic
+    /// automaton
+    pub fn from_jff(xml: &str) -> Result<Dfa, String> {
+        let (states, transitions, q_start, q_accepting) = from_jff(xml)?;
+        let mut dfa_transitions: Vec<(usize, Symbol, usize)> =
+            Vec::with_capacity(transitions.len());
+        for (from, read, to) in transitions {
+            let chars: Vec<char> = read.chars().collect();
+            match chars[..] {
+                [] => dfa_transitions.push((from, Symbol::EPSILON, to)),
+                [c] => dfa_transitions.push((from, Symbol::CHAR(c), to)),
+                _ => {
+                    return Err(format!(
+                        "A Dfa can't read more than one symbol at once (\"{}\")",
+                        read
+                    ));
+                }
+            }
+        }
+        dfa_from_parts(states, dfa_transitions, q_start, q_accepting)
+    }
+}
+
+fn to_jff(
+    states: &[usize],
+    q_start: usize,
+    q_accepting: &HashSet<usize>,
+    transitions: impl Iterator<Item = (usize, Symbol, usize)>,
+) -> String {
+    let mut states = states.to_vec();
+    states.sort();
+    let mut transitions: Vec<(usize, Symbol, usize)> = transitions
+        .filter(|(_, with, _)| *with != Symbol::EMPTY)
+        .collect();
+    transitions.sort_by_key(|(from, with, to)| {
+        let read = match with {
+            Symbol::CHAR(c) => Some(*c),
+            _ => None,
+        };
+        (*from, *to, read)
+    });
+
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>\n<structure>\n\t<type>fa</type>\n\t<automaton>\n",
+    );
+    for (i, state) in states.iter().enumerate() {
+        xml.push_str(&format!(
+            "\t\t<state id=\"{}\" name=\"q{}\">\n\t\t\t<x>{}.0</x>\n\t\t\t<y>{}.0</y>\n",
+            state,
+            state,
+            SPACING / 2 + SPACING * (i % COLUMNS),
+            SPACING / 2 + SPACING * (i / COLUMNS)
+        ));
+        if *state == q_start {
+            xml.push_str("\t\t\t<initial/>\n");
+        }
+        if q_accepting.contains(state) {
+            xml.push_str("\t\t\t<final/>\n");
+        }
+        xml.push_str("\t\t</state>\n");
+    }
+    for (from, with, to) in transitions {
+        let read = match with {
+            Symbol::CHAR(c) => format!("<read>{}</read>", escape(c)),
+            _ => String::from("<read/>"),
+        };
+        xml.push_str(&format!(
+            "\t\t<transition>\n\t\t\t<from>{}</from>\n\t\t\t<to>{}</to>\n\t\t\t{}\n\t\t</transition>\n",
+            from, to, read
+        ));
+    }
+    xml.push_str("\t</automaton>\n</structure>\n");
+    xml
+}
+
+fn escape(c: char) -> String {
+    match c {
+        '&' => String::from("&amp;"),
+        '<' => String::from("&lt;"),
+        '>' => String::from("&gt;"),
+        // leading and trailing whitespace would be lost to most xml tooling otherwise
+        c if c.is_whitespace() => format!("&#{};", c as u32),
+        c => c.to_string(),
+    }
+}
+
+type Parsed = (
+    Vec<usize>,
+    Vec<(usize, String, usize)>,
+    usize,
+    HashSet<usize>,
+);
+
+/// Parse `xml` and replace all states with fresh ones. Transitions keep what they read as is.
+fn from_jff(xml: &str) -> Result<Parsed, String> {
+    let document = Document::parse(xml).map_err(|e| e.to_string())?;
+    let structure = document.root_element();
+    if structure.tag_name().name() != "structure" {
+        return Err(String::from("Not a JFLAP file"));
+    }
+    match child_text(structure, "type") {
+        Some("fa") => {}
+        Some(other) => {
+            return Err(format!(
+                "Only finite automata are supported, not \"{}\"",
+                other
+            ));
+        }
+        None => return Err(String::from("Missing <type>")),
+    }
+    let automaton = children(structure, "automaton").next().unwrap_or(structure);
+
+    let mut ids: HashMap<&str, usize> = HashMap::new();
+    let mut states: Vec<usize> = Vec::new();
+    let mut q_start: Option<usize> = None;
+    let mut q_accepting: HashSet<usize> = HashSet::new();
+    for state in children(automaton, "state") {
+        let name = state
+            .attribute("id")
+            .ok_or_else(|| String::from("<state> without id"))?;
+        if ids.contains_key(name) {
+            return Err(format!("State {} exists twice", name));
+        }
+        let id = next_state();
+        ids.insert(name, id);
+        states.push(id);
+        if children(state, "initial").next().is_some() {
+            if q_start.is_some() {
+                return Err(String::from("More than one initial state"));
+            }
+            q_start = Some(id);
+        }
+        if children(state, "final").next().is_some() {
+            q_accepting.insert(id);
+        }
+    }
+    let q_start = q_start.ok_or_else(|| String::from("No initial state"))?;
+
+    let id = |transition: Node, tag: &'static str| -> Result<usize, String> {
+        let name =
+            child_text(transition, tag).ok_or_else(|| format!("<transition> without <{}>", tag))?;
+        ids.get(name.trim())
+            .cloned()
+            .ok_or_else(|| format!("State {} ∉ Q", name))
+    };
+    let mut transitions: Vec<(usize, String, usize)> = Vec::new();
+    for transition in children(automaton, "transition") {
+        let read = children(transition, "read")
+            .next()
+            .ok_or_else(|| String::from("<transition> without <read>"))?;
+        transitions.push((
+            id(transition, "from")?,
+            read.text().unwrap_or("").to_string(),
+            id(transition, "to")?,
+        ));
+    }
+
+    Ok((states, transitions, q_start, q_accepting))
+}
+
+fn children<'a, 'input>(
+    node: Node<'a, 'input>,
+    tag: &'static str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children()
+        .filter(move |child| child.is_element() && child.tag_name().name() == tag)
+}
+
+fn child_text<'a>(node: Node<'a, '_>, tag: &'static str) -> Option<&'a str> {
+    children(node, tag)
+        .next()
+        .map(|child| child.text().unwrap_or(""))
+}
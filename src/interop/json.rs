@@ -0,0 +1,248 @@
+use crate::automata::{Dfa, Nfa, Symbol, next_state};
+use crate::interop::dfa_from_parts;
+use crate::render::class_items;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+///////////////////////////////////////////////////////// JSON /////////////////////////////////////////////////////////
+// Automata are exchanged as a single JSON object (see `automaton.schema.json` next to this file for the schema):
+//
+// {
+//     "type": "nfa",
+//     "states": [0, 1, 2],
+//     "start": 0,
+//     "accepting": [2],
+//     "transitions": [
+//         { "from": 0, "to": 1, "symbol": "a" },
+//         { "from": 1, "to": 2, "epsilon": true },
+//         { "from": 2, "to": 2, "class": ["a-f", "x"] }
+//     ]
+// }
+//
+// - `type` is either `"nfa"` or `"dfa"`. A `"dfa"` must neither contain epsilon transitions nor two transitions
+//   leaving the same state with the same symbol.
+// - States are arbitrary non-negative integers. They are replaced by fresh ids on import, so an imported automaton
+//   never clashes with the ones constructed by this crate.
+// - Every transition has exactly one label: a single `symbol`, `epsilon` (always `true`), or a `class`. A class is a
+//   list of single characters and inclusive ranges written as `"a-f"`. It is a shorthand for one transition per
+//   character, so `{ "class": ["a-c"] }` is the same as three transitions labelled `a`, `b` and `c`.
+//
+// On export, parallel transitions between two states are merged into a class, states and transitions are sorted.
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Kind {
+    Nfa,
+    Dfa,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AutomatonJson {
+    #[serde(rename = "type")]
+    kind: Kind,
+    states: Vec<usize>,
+    start: usize,
+    accepting: Vec<usize>,
+    transitions: Vec<TransitionJson>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct TransitionJson {
+    from: usize,
+    to: usize,
+    // exactly one label, as a tagged enum can't be flattened without silently accepting several of them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    symbol: Option<char>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    epsilon: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    class: Option<Vec<String>>,
+}
+
+impl Nfa {
+    /// Export `self` as JSON, see `json.rs` for the format.
+    pub fn to_json(&self) -> String {
+        to_json(
+            Kind::Nfa,
+            &self.states,
+            self.q_start,
+            &self.q_accepting,
+            self.transitions.iter().cloned(),
+        )
+    }
+
+    /// Import an `Nfa` from JSON, see `json.rs` for the format. Both `"nfa"` and `"dfa"` are accepted.
+    /// returns: Err(reason) if `json` is malformed or describes an invalid automaton
+    pub fn from_json(json: &str) -> Result<Nfa, String> {
+        let (_, states, transitions, q_start, q_accepting) = from_json(json)?;
+        Nfa::try_new(
+            states,
+            transitions.into_iter().collect(),
+            q_start,
+            q_accepting,
+        )
+    }
+}
+
+impl Dfa {
+    /// Export `self` as JSON, see `json.rs` for the format.
+    pub fn to_json(&self) -> String {
+        to_json(
+            Kind::Dfa,
+            self.states(),
+            self.q_start,
+            self.q_accepting(),
+            self.transitions()
+                .iter()
+                .map(|((from, with), to)| (*from, *with, *to)),
+        )
+    }
+
+    /// Import a `Dfa` from JSON, see `json.rs` for the format. Only `"dfa"` is accepted.
+    /// returns: Err(reason) if `json` is malformed or describes an invalid or non-deterministic automaton
+    pub fn from_json(json: &str) -> Result<Dfa, String> {
+        let (kind, states, transitions, q_start, q_accepting) = from_json(json)?;
+        if !matches!(kind, Kind::Dfa) {
+            return Err(String::from("Expected an automaton of type \"dfa\""));
+        }
+        dfa_from_parts(states, transitions, q_start, q_accepting)
+    }
+}
+
+fn to_json(
+    kind: Kind,
+    states: &[usize],
+    q_start: usize,
+    q_accepting: &HashSet<usize>,
+    transitions: impl Iterator<Item = (usize, Symbol, usize)>,
+) -> String {
+    let mut states = states.to_vec();
+    states.sort();
+    let mut accepting: Vec<usize> = q_accepting.iter().cloned().collect();
+    accepting.sort();
+
+    let mut chars: BTreeMap<(usize, usize), BTreeSet<char>> = BTreeMap::new();
+    let mut epsilons: BTreeSet<(usize, usize)> = BTreeSet::new();
+    for (from, with, to) in transitions {
+        match with {
+            Symbol::CHAR(c) => {
+                chars.entry((from, to)).or_default().insert(c);
+            }
+            Symbol::EPSILON => {
+                epsilons.insert((from, to));
+            }
+            // the empty language has no transitions to begin with
+            Symbol::EMPTY => {}
+        }
+    }
+    let mut transitions: Vec<TransitionJson> = Vec::new();
+    for ((from, to), chars) in chars {
+        let mut transition = TransitionJson {
+            from,
+            to,
+            ..Default::default()
+        };
+        if chars.len() == 1 {
+            transition.symbol = chars.first().cloned();
+        } else {
+            transition.class = Some(class_items(&chars));
+        }
+        transitions.push(transition);
+    }
+    for (from, to) in epsilons {
+        transitions.push(TransitionJson {
+            from,
+            to,
+            epsilon: Some(true),
+            ..Default::default()
+        });
+    }
+    transitions.sort_by_key(|t| (t.from, t.to));
+
+    let json = AutomatonJson {
+        kind,
+        states,
+        start: q_start,
+        accepting,
+        transitions,
+    };
+    serde_json::to_string_pretty(&json).expect("automata always serialize")
+}
+
+type Parsed = (
+    Kind,
+    Vec<usize>,
+    Vec<(usize, Symbol, usize)>,
+    usize,
+    HashSet<usize>,
+);
+
+/// Parse `json` and replace all states with fresh ones.
+fn from_json(json: &str) -> Result<Parsed, String> {
+    let parsed: AutomatonJson = serde_json::from_str(json).map_err(|e| e.to_string())?;
+
+    let mut ids: HashMap<usize, usize> = HashMap::new();
+    let mut states: Vec<usize> = Vec::with_capacity(parsed.states.len());
+    for state in &parsed.states {
+        if ids.contains_key(state) {
+            return Err(format!("State {} exists twice", state));
+        }
+        let id = next_state();
+        ids.insert(*state, id);
+        states.push(id);
+    }
+    let id = |state: &usize| -> Result<usize, String> {
+        ids.get(state)
+            .cloned()
+            .ok_or_else(|| format!("State {} ∉ Q", state))
+    };
+
+    let mut transitions: Vec<(usize, Symbol, usize)> = Vec::new();
+    for transition in &parsed.transitions {
+        let (from, to) = (id(&transition.from)?, id(&transition.to)?);
+        match (&transition.symbol, &transition.epsilon, &transition.class) {
+            (Some(c), None, None) => transitions.push((from, Symbol::CHAR(*c), to)),
+            (None, Some(true), None) => transitions.push((from, Symbol::EPSILON, to)),
+            (None, Some(false), None) => {
+                return Err(String::from("\"epsilon\" must be true if present"));
+            }
+            (None, None, Some(items)) => {
+                for c in parse_class(items)? {
+                    transitions.push((from, Symbol::CHAR(c), to));
+                }
+            }
+            _ => {
+                return Err(format!(
+                    "Transition {} -> {} needs exactly one of \"symbol\", \"epsilon\" or \"class\"",
+                    transition.from, transition.to
+                ));
+            }
+        }
+    }
+    let q_start = id(&parsed.start)?;
+    let q_accepting = parsed
+        .accepting
+        .iter()
+        .map(id)
+        .collect::<Result<HashSet<usize>, String>>()?;
+
+    Ok((parsed.kind, states, transitions, q_start, q_accepting))
+}
+
+fn parse_class(items: &[String]) -> Result<Vec<char>, String> {
+    let mut chars: Vec<char> = Vec::new();
+    for item in items {
+        let item_chars: Vec<char> = item.chars().collect();
+        match item_chars[..] {
+            [c] => chars.push(c),
+            [start, '-', end] if start <= end => chars.extend(start..=end),
+            _ => return Err(format!("Invalid class item \"{}\"", item)),
+        }
+    }
+    if chars.is_empty() {
+        return Err(String::from("Empty class"));
+    }
+    Ok(chars)
+}
@@ -0,0 +1,10 @@
+pub mod automata;
+pub mod codegen;
+mod interop;
+pub mod lint;
+pub mod matcher;
+pub mod parse;
+mod render;
+
+#[cfg(test)]
+mod test;
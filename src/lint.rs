@@ -0,0 +1,271 @@
+use crate::automata::{Dfa, Nfa, Symbol};
+use crate::parse::{
+    _alphabet, _expr, _tokenize_disjunct, _tokenize_root_level, ParseError, try_parse,
+};
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+/////////////////////////////////////////////////////// LINTING ////////////////////////////////////////////////////////
+// A pattern can be perfectly valid and still not mean what its author had in mind. The linter walks the pattern the same
+// way `parse` does and compiles every alternative and quantified group on its own, so the automata can tell which parts
+// of it don't contribute anything. Fragments are compiled with respect to the alphabet of the entire pattern, just like
+// `parse` does, otherwise a `~` inside of them would mean something else.
+
+/// Something about a pattern that is most likely a mistake, see `lint`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lint {
+    pub kind: LintKind,
+    pub message: String,
+    /// The byte range of the offending part of the pattern
+    pub span: Range<usize>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LintKind {
+    /// An alternative only matches words other alternatives match anyway, e.g. `a` in `a|a*`
+    Redundant,
+    /// A quantifier is applied to something that already matches the empty word, e.g. `(a?)*`
+    QuantifiedEmpty,
+    /// A part of the pattern that doesn't match any word at all, e.g. `a&b`
+    NeverMatches,
+    /// The entire pattern matches the empty word by accident, as one of its alternatives does or every part of it is
+    /// optional, e.g. `a|b*` or `a?b*`. `find_all` never reports empty matches, but `rdfa` selects every line for it.
+    MatchesEmptyWord,
+}
+
+impl Display for Lint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at {}..{}",
+            self.message, self.span.start, self.span.end
+        )
+    }
+}
+
+/// Check `pattern` for constructs that are valid, but most likely not what was meant:
+/// - alternatives that don't add anything to the others, like `a` in `a|a*` or the second `ab` in `ab|c|ab`
+/// - quantifiers on something that already matches the empty word, like `(a?)*`, which is just `a*`
+/// - parts of the pattern that can never match, like `a&b`
+/// - the entire pattern matching the empty word by accident, like `a*|b` or `a?b?` do, but not on purpose like `a*`
+///
+/// returns: The lints ordered by their position in `pattern`, or why it can't be parsed at all
+pub fn lint(pattern: &str) -> Result<Vec<Lint>, ParseError> {
+    let nfa = try_parse(pattern)?;
+    let mut linter = Linter {
+        pattern,
+        alphabet: _alphabet(pattern),
+        lints: Vec::new(),
+    };
+    linter.expr(pattern, 0);
+
+    if nfa.accepts_empty_word()
+        && let Some((message, span)) = linter.empty_by_accident(pattern, 0)
+    {
+        linter.report(LintKind::MatchesEmptyWord, message, span);
+    }
+
+    let mut lints = linter.lints;
+    lints.sort_by_key(|lint| (lint.span.start, lint.span.end));
+    Ok(lints)
+}
+
+struct Linter<'a> {
+    pattern: &'a str,
+    alphabet: HashSet<Symbol>,
+    lints: Vec<Lint>,
+}
+
+impl Linter<'_> {
+    fn report(&mut self, kind: LintKind, message: String, span: Range<usize>) {
+        self.lints.push(Lint {
+            kind,
+            message,
+            span,
+        });
+    }
+
+    /// Compile the `fragment` of the pattern starting at `offset` like `parse` would as part of the entire pattern
+    fn compile(&self, fragment: &str, offset: usize) -> Nfa {
+        _expr(fragment, offset, &self.alphabet)
+    }
+
+    fn expr(&mut self, expr: &str, offset: usize) {
+        let alternatives = alternatives(expr, offset);
+        let nfas: Vec<Nfa> = alternatives
+            .iter()
+            .map(|(alternative, start)| self.compile(alternative, *start))
+            .collect();
+
+        let mut live = vec![true; alternatives.len()];
+        for (i, (alternative, start)) in alternatives.iter().enumerate() {
+            let span = *start..start + alternative.len();
+            if !nfas[i].is_empty() {
+                self.term(alternative, *start);
+                continue;
+            }
+            live[i] = false;
+            // only report the innermost part that never matches, as that is where the mistake is
+            let reported = self.count(LintKind::NeverMatches);
+            self.term(alternative, *start);
+            if self.count(LintKind::NeverMatches) == reported {
+                let message = format!("`{}` never matches anything", &self.pattern[span.clone()]);
+                self.report(LintKind::NeverMatches, message, span);
+            }
+        }
+
+        // Going backwards, an alternative is redundant if all of its words are matched by the alternatives that are
+        // still left. This way only the later of two equal alternatives is reported, and of `a|a*` only the `a`.
+        let dfas: Vec<Dfa> = nfas.iter().map(Dfa::from).collect();
+        for i in (0..alternatives.len()).rev() {
+            if !live[i] {
+                continue;
+            }
+            let others: Vec<usize> = (0..alternatives.len())
+                .filter(|j| *j != i && live[*j])
+                .collect();
+            // a single alternative covering it is much more helpful to point at than all of them at once
+            let message = match others
+                .iter()
+                .find(|j| dfas[i].difference(&dfas[**j]).is_empty())
+            {
+                Some(j) => format!(
+                    "`{}` is already matched by `{}`",
+                    alternatives[i].0, alternatives[*j].0
+                ),
+                None if others.len() > 1
+                    && others[1..]
+                        .iter()
+                        .fold(dfas[i].difference(&dfas[others[0]]), |rest, j| {
+                            rest.difference(&dfas[*j])
+                        })
+                        .is_empty() =>
+                {
+                    format!(
+                        "`{}` is already matched by the other alternatives",
+                        alternatives[i].0
+                    )
+                }
+                None => continue,
+            };
+            live[i] = false;
+            let (alternative, start) = &alternatives[i];
+            self.report(
+                LintKind::Redundant,
+                message,
+                *start..start + alternative.len(),
+            );
+        }
+    }
+
+    fn term(&mut self, term: &str, offset: usize) {
+        let mut start = offset;
+        for disjunct in _tokenize_root_level(term, '&') {
+            self.disjunct(&disjunct, start);
+            start += disjunct.len() + 1;
+        }
+    }
+
+    fn disjunct(&mut self, disjunct: &str, offset: usize) {
+        let mut start = offset;
+        for factor in _tokenize_disjunct(disjunct) {
+            self.factor(&factor, start);
+            start += factor.len();
+        }
+    }
+
+    fn factor(&mut self, factor: &str, offset: usize) {
+        if let Some(complemented) = factor.strip_prefix('~') {
+            return self.factor(complemented, offset + 1);
+        }
+        let quantifier = factor
+            .chars()
+            .last()
+            .filter(|c| ['*', '+', '?'].contains(c));
+        let (Some(quantifier), true) = (quantifier, factor.len() > 1) else {
+            return self.atom(factor, offset);
+        };
+        let atom = &factor[..factor.len() - 1];
+        if self.compile(atom, offset).accepts_empty_word() {
+            let message = match quantifier {
+                '?' => format!(
+                    "`{}` already matches the empty word, so `?` makes no difference",
+                    atom
+                ),
+                '+' => format!(
+                    "`{}` already matches the empty word, so `+` is the same as `*`",
+                    atom
+                ),
+                _ => format!(
+                    "`{}` already matches the empty word, which `*` does anyway",
+                    atom
+                ),
+            };
+            self.report(
+                LintKind::QuantifiedEmpty,
+                message,
+                offset..offset + factor.len(),
+            );
+        }
+        self.atom(atom, offset);
+    }
+
+    fn atom(&mut self, atom: &str, offset: usize) {
+        if atom.chars().count() > 1 {
+            self.expr(&atom[1..atom.len() - 1], offset + 1);
+        }
+    }
+
+    /// Why `expr`, which matches the empty word, most likely does so by accident. A single quantified or complemented
+    /// factor like `a*` says so on purpose, but one of several alternatives or a sequence of optional parts hardly does.
+    fn empty_by_accident(&self, expr: &str, offset: usize) -> Option<(String, Range<usize>)> {
+        let alternatives = alternatives(expr, offset);
+        if alternatives.len() > 1 {
+            // point at the alternative responsible for it
+            let (alternative, start) = alternatives.iter().find(|(alternative, start)| {
+                self.compile(alternative, *start).accepts_empty_word()
+            })?;
+            let message = format!(
+                "The pattern matches the empty word, because `{}` does",
+                alternative
+            );
+            return Some((message, *start..start + alternative.len()));
+        }
+
+        let (term, start) = &alternatives[0];
+        if _tokenize_root_level(term, '&').len() > 1 {
+            return None;
+        }
+        let factors = _tokenize_disjunct(term);
+        match factors.as_slice() {
+            // a group on its own, e.g. the entire pattern in parentheses
+            [factor] if factor.starts_with('(') && factor.ends_with(')') => {
+                self.empty_by_accident(&factor[1..factor.len() - 1], start + 1)
+            }
+            [_] => None,
+            _ => Some((
+                String::from("The pattern matches the empty word, as every part of it is optional"),
+                *start..start + term.len(),
+            )),
+        }
+    }
+
+    fn count(&self, kind: LintKind) -> usize {
+        self.lints.iter().filter(|lint| lint.kind == kind).count()
+    }
+}
+
+/// Split `expr` into its alternatives, each with the offset it starts at
+fn alternatives(expr: &str, offset: usize) -> Vec<(String, usize)> {
+    let mut start = offset;
+    _tokenize_root_level(expr, '|')
+        .into_iter()
+        .map(|alternative| {
+            let at = start;
+            // every alternative is followed by a single byte '|'
+            start += alternative.len() + 1;
+            (alternative, at)
+        })
+        .collect()
+}
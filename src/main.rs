@@ -1,16 +1,13 @@
-
-use crate::automata::{Automaton, Dfa};
-use crate::benchmark::benchmark_dfa::benchmark_dfa_klenee;
-use crate::parse::parse;
-
-mod automata;
-mod parse;
-
-#[cfg(test)]
-mod test;
-mod benchmark;
-
-fn main() {
-    benchmark_dfa_klenee(20000, 100);
-
+use clap::Parser;
+use std::process::ExitCode;
+
+mod cli;
+
+fn main() -> ExitCode {
+    // grep takes the pattern as first argument, so `repl` can't be a regular subcommand next to it.
+    // A pattern that happens to be "repl" can still be searched for with `rdfa -- repl`.
+    if std::env::args().nth(1).as_deref() == Some("repl") {
+        return cli::repl::run();
+    }
+    cli::grep::run(&cli::grep::Args::parse())
 }
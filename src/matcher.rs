@@ -0,0 +1,41 @@
+use crate::automata::{Automaton, Dfa};
+use crate::parse::{ParseError, try_parse};
+
+pub mod set;
+
+/// A pattern compiled into the pair of `Dfa`s it takes to match _and_ find it: the `Dfa` of the pattern itself
+/// and the one of the reversed pattern, which `Dfa::find_all` runs first on the reversed input.
+/// This hides the reversing from everything that just wants to search text, e.g. the FFI or the command line tool.
+pub struct Matcher {
+    pub dfa: Dfa,
+    pub reversed: Dfa,
+}
+
+impl Matcher {
+    /// Parse and compile `pattern`.
+    pub fn new(pattern: &str) -> Result<Matcher, ParseError> {
+        let nfa = try_parse(pattern)?;
+        Ok(Matcher {
+            reversed: Dfa::from(&nfa.reversed().to_finding()),
+            dfa: Dfa::from(&nfa),
+        })
+    }
+
+    /// Whether all of `input` is accepted.
+    pub fn is_match(&self, input: &str) -> bool {
+        self.dfa.accept(input)
+    }
+
+    /// All matches of the pattern in `input` as inclusive `(start, end)` char indices, in order.
+    pub fn find_all(&self, input: &str) -> Vec<(usize, usize)> {
+        let input_reversed: String = input.chars().rev().collect();
+        self.reversed
+            .find_all(&input_reversed, &self.dfa)
+            .unwrap_or_default()
+    }
+
+    /// The first match of the pattern in `input`, see `Matcher::find_all`.
+    pub fn find(&self, input: &str) -> Option<(usize, usize)> {
+        self.find_all(input).first().cloned()
+    }
+}
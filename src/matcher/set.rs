@@ -0,0 +1,190 @@
+use crate::automata::{Dfa, Nfa, Symbol, next_state};
+use crate::parse::{ParseError, try_parse};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+/////////////////////////////////////////////////////// REGEX SETS ///////////////////////////////////////////////////////
+// Instead of running one `Dfa` per pattern, all of them are combined into a single `Nfa` with a new start state leading
+// to the start of each of them. The powerset construction then runs all patterns in lockstep, and as every `Dfa` state
+// knows the `Nfa` states it stands for, it also knows the patterns whose accepting states are among them.
+//
+// To find the patterns anywhere in the input, a run has to start over at every position while the runs started
+// before go on. Adding a loop to the start of the `Nfa` does that, but then every `Dfa` state contains the starts of
+// all patterns, making the construction quadratic in their number. Going through the sets of `Dfa` states that are
+// running at the same time instead, each of them already stands for all patterns.
+
+/// Many patterns compiled into a single `Dfa`, which tells which of the patterns match in a single pass over the input.
+pub struct RegexSet {
+    /// Accepts the words matched by any of the patterns
+    pub dfa: Dfa,
+    /// Accepts the words ending in a match of any of the patterns, to find them anywhere in a single pass
+    pub searching: Dfa,
+    /// The indices of the patterns every accepting state of `dfa` and `searching` accepts for
+    pub tags: HashMap<usize, BTreeSet<usize>>,
+    len: usize,
+}
+
+/// A pattern of a `RegexSet` that can't be parsed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SetError {
+    /// The index of the pattern
+    pub index: usize,
+    pub error: ParseError,
+}
+
+impl Display for SetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "pattern {}: {}", self.index, self.error)
+    }
+}
+
+impl std::error::Error for SetError {}
+
+impl RegexSet {
+    /// Parse and compile all of `patterns`, which are identified by their index from then on.
+    pub fn new<I, S>(patterns: I) -> Result<RegexSet, SetError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let nfas = patterns
+            .into_iter()
+            .enumerate()
+            .map(|(index, pattern)| {
+                try_parse(pattern.as_ref()).map_err(|error| SetError { index, error })
+            })
+            .collect::<Result<Vec<Nfa>, SetError>>()?;
+
+        // the pattern every accepting state belongs to, as the state ids of all `Nfa`s are distinct
+        let pattern_of: HashMap<usize, usize> = nfas
+            .iter()
+            .enumerate()
+            .flat_map(|(index, nfa)| nfa.q_accepting.iter().map(move |q| (*q, index)))
+            .collect();
+
+        // a single start state instead of a chain of `Nfa::union`s, whose epsilon closures would get as deep as there
+        // are patterns
+        let q_start = next_state();
+        let mut nfa = Nfa::new(vec![q_start], HashSet::new(), q_start, HashSet::new());
+        for other in &nfas {
+            nfa.states.extend(&other.states);
+            nfa.transitions.extend(other.transitions.iter().cloned());
+            nfa.alphabet.extend(other.alphabet.iter().cloned());
+            nfa.q_accepting.extend(&other.q_accepting);
+            nfa.transitions
+                .insert((q_start, Symbol::EPSILON, other.q_start));
+        }
+        let dfa = Dfa::from_with_provenance(&nfa);
+        let mut tags: HashMap<usize, BTreeSet<usize>> = dfa
+            .q_accepting()
+            .iter()
+            .map(|state| {
+                let patterns = dfa.nfa_states()[state]
+                    .iter()
+                    .filter_map(|q| pattern_of.get(q).cloned())
+                    .collect();
+                (*state, patterns)
+            })
+            .collect();
+        let searching = searching(&dfa, &mut tags);
+
+        Ok(RegexSet {
+            // the tags are all that's needed of the `Nfa` states from here on
+            dfa: dfa.without_nfa_states(),
+            searching,
+            tags,
+            len: nfas.len(),
+        })
+    }
+
+    /// The number of patterns in `self`
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The indices of the patterns accepting all of `input`, in ascending order.
+    pub fn matches(&self, input: &str) -> Vec<usize> {
+        let mut state = self.dfa.q_start;
+        for c in input.chars() {
+            match self.dfa.transitions().get(&(state, Symbol::CHAR(c))) {
+                Some(next) => state = *next,
+                None => return Vec::new(),
+            }
+        }
+        self.tags_of(state).cloned().collect()
+    }
+
+    /// The indices of the patterns matching somewhere in `input`, in ascending order.
+    pub fn matches_in(&self, input: &str) -> Vec<usize> {
+        let mut state = self.searching.q_start;
+        let mut matched: BTreeSet<usize> = self.tags_of(state).cloned().collect();
+        for c in input.chars() {
+            // no match contains a symbol that isn't in any of the patterns, so they all start over after it
+            state = match self.searching.transitions().get(&(state, Symbol::CHAR(c))) {
+                Some(next) => *next,
+                None => self.searching.q_start,
+            };
+            matched.extend(self.tags_of(state));
+            if matched.len() == self.len {
+                break;
+            }
+        }
+        matched.into_iter().collect()
+    }
+
+    fn tags_of(&self, state: usize) -> impl Iterator<Item = &usize> {
+        self.tags.get(&state).into_iter().flatten()
+    }
+}
+
+/// Construct the `Dfa` accepting every word ending in a word accepted by `dfa`, and tag its accepting states with the
+/// union of the `tags` of the states of `dfa` they stand for.
+fn searching(dfa: &Dfa, tags: &mut HashMap<usize, BTreeSet<usize>>) -> Dfa {
+    let mut successors: HashMap<usize, Vec<(Symbol, usize)>> = HashMap::new();
+    for ((from, with), to) in dfa.transitions() {
+        successors.entry(*from).or_default().push((*with, *to));
+    }
+
+    // the states of `dfa` a state stands for, where the start is always one of them as a match may start anywhere
+    let start = BTreeSet::from([dfa.q_start]);
+    let q_start = next_state();
+    let mut ids: HashMap<BTreeSet<usize>, usize> = HashMap::from([(start.clone(), q_start)]);
+    let mut queue: Vec<BTreeSet<usize>> = vec![start];
+    let mut transitions: HashMap<(usize, Symbol), usize> = HashMap::new();
+    let mut q_accepting: HashSet<usize> = HashSet::new();
+    while let Some(set) = queue.pop() {
+        let id = ids[&set];
+        if set.iter().any(|q| dfa.q_accepting().contains(q)) {
+            let patterns: BTreeSet<usize> = set
+                .iter()
+                .filter_map(|q| tags.get(q))
+                .flatten()
+                .cloned()
+                .collect();
+            q_accepting.insert(id);
+            tags.insert(id, patterns);
+        }
+
+        let mut targets: HashMap<Symbol, BTreeSet<usize>> = HashMap::new();
+        for state in &set {
+            for (with, to) in successors.get(state).into_iter().flatten() {
+                targets.entry(*with).or_default().insert(*to);
+            }
+        }
+        for (with, mut target) in targets {
+            target.insert(dfa.q_start);
+            let to = *ids.entry(target.clone()).or_insert_with(|| {
+                queue.push(target);
+                next_state()
+            });
+            transitions.insert((id, with), to);
+        }
+    }
+
+    let states: Vec<usize> = ids.into_values().collect();
+    Dfa::new(states, transitions, q_start, q_accepting)
+}
@@ -1,50 +1,203 @@
-use crate::automata::{Automaton, Nfa, Symbol};
+use crate::automata::{Automaton, Dfa, Nfa, Symbol};
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+/// Symbols with a special meaning in a pattern. Every other character is a literal.
+const RESERVED: [char; 8] = ['(', ')', '|', '&', '~', '*', '+', '?'];
 
 /// Parse `pattern` into a Non-deterministic Finite Automaton.
 ///
 /// Uses an algorithm is an optimized version of a recursive descent, constructed over a grammar where
 /// choices can be made deterministically without a need for backtracking.
 /// The pattern is parsed by order of precedence according to the following grammar:
-/// ```
-/// EXPR -> EXPR|DISJUNCT / DISJUNCT
+/// ```text
+/// EXPR -> EXPR|TERM / TERM
+/// TERM -> TERM&DISJUNCT / DISJUNCT
 /// DISJUNCT -> DISJUNCT FACTOR / FACTOR
-/// FACTOR -> ATOM* / ATOM
+/// FACTOR -> ~FACTOR / ATOM* / ATOM
 /// ATOM -> (EXPR) / symbol
 /// ```
 /// Where `EXPR` is the start symbol.
+/// So from loosest to tightest binding: `|`, `&`, concatenation, `~`, quantifiers. This means `~ab&c|d` is read as
+/// `((~a)b&c)|d`, while `~a*` is read as `~(a*)`.
 ///
 /// For every NTS, a corresponding function exists tokenizing its passed string according to its production rules.
 /// In order to ensure explicit precedence through parantheses, a stack is used to keep track of opening and closing
 /// brackets encountered so far.
 /// For example, the string `(a|b)|c` will be parsed by `_expr` into the strings `(a|b)` and `c`. These are then passed
-/// to the `_disjunct` function, and the returned NFAs are unionized.
+/// to the `_term` function, and the returned NFAs are unionized.
+///
+/// Intersection (`r&s`) and complement (`~r`) can't be expressed with the thompson-like constructions on `Nfa`, so their
+/// operands are compiled to `Dfa`s and combined via a product construction / by complementing the `Dfa`.
+/// A complement is always taken with respect to the alphabet of the _entire_ pattern, so `~a` in `~a&(a|b)*` accepts
+/// every word over `{a, b}` except for `a`. Symbols not occurring in the pattern are never accepted.
 ///
 /// Note that there is a recursive pattern hidden here: An atom can be either a symbol, or a fully quallified
 /// Regular Expression inside of parantheses. This recursion should not cause any overflows, as there is no backtracking
 /// involved.
 pub fn parse(pattern: &str) -> Nfa {
-    _expr(pattern)
+    _expr(pattern, 0, &_alphabet(pattern))
+}
+
+/// The symbols occurring in `pattern`, which is what `~` complements with respect to.
+pub(crate) fn _alphabet(pattern: &str) -> HashSet<Symbol> {
+    pattern
+        .chars()
+        .filter(|c| !RESERVED.contains(c))
+        .map(Symbol::CHAR)
+        .collect()
+}
+
+/// An invalid pattern, with the byte range of the offending part of it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    pub span: Range<usize>,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at {}..{}",
+            self.message, self.span.start, self.span.end
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Same as `parse`, but reports an invalid `pattern` instead of panicking.
+pub fn try_parse(pattern: &str) -> Result<Nfa, ParseError> {
+    _check(pattern)?;
+    Ok(parse(pattern))
+}
+
+/// Find everything `parse` would choke on in a single pass over `pattern`.
+/// Once this passes, the recursive descent itself can rely on a well-formed pattern.
+fn _check(pattern: &str) -> Result<(), ParseError> {
+    let error = |message: &str, span: Range<usize>| {
+        Err(ParseError {
+            message: String::from(message),
+            span,
+        })
+    };
+    // positions of the brackets that are still open
+    let mut brackets: Vec<usize> = Vec::new();
+    // the last char that isn't part of an operand yet, i.e. after which an operand has to follow
+    let mut pending: Option<(usize, char)> = Some((0, '^'));
+    let mut quantified = false;
+
+    for (pos, c) in pattern.char_indices() {
+        let here = pos..pos + c.len_utf8();
+        match c {
+            '*' | '+' | '?' => {
+                match pending {
+                    Some((_, '~')) => return error("Nothing to complement", here),
+                    Some(_) => return error("Nothing to quantify", here),
+                    None if quantified => return error("Illegal stacking of quantifiers", here),
+                    None => {}
+                }
+                quantified = true;
+                continue;
+            }
+            ')' | '|' | '&' => {
+                if c == ')' && brackets.is_empty() {
+                    return error("Unexpected ')'", here);
+                }
+                match pending {
+                    Some((at, '~')) => return error("Nothing to complement", at..at + 1),
+                    Some((at, '(')) if c == ')' => return error("Empty group", at..pos + 1),
+                    Some(_) => return error("Empty alternative", here),
+                    None => {}
+                }
+                pending = if c == ')' {
+                    brackets.pop();
+                    None
+                } else {
+                    Some((pos, c))
+                };
+            }
+            '(' => {
+                brackets.push(pos);
+                pending = Some((pos, c));
+            }
+            '~' => pending = Some((pos, c)),
+            _ => pending = None,
+        }
+        quantified = false;
+    }
+
+    match pending {
+        Some((at, '~')) => error("Nothing to complement", at..at + 1),
+        Some((at, '(')) => error("Unclosed '('", at..at + 1),
+        Some(_) if pattern.is_empty() => error("Empty pattern", 0..0),
+        Some(_) => error("Empty alternative", pattern.len()..pattern.len()),
+        None => match brackets.pop() {
+            Some(at) => error("Unclosed '('", at..at + 1),
+            None => Ok(()),
+        },
+    }
 }
 
-fn _expr(pattern: &str) -> Nfa {
-    let tokens: Vec<String> = _tokenize_expr(pattern);
-    let mut nfa = _disjunct(&tokens[0]);
+// Every function gets the byte `offset` of its part of the pattern, so the states it creates can be labelled with their
+// span. Labelling only ever fills in states without a span, and as the innermost constructions are labelled first,
+// every state ends up with the span of the smallest sub-pattern it was created for, e.g. the state introduced by `*`
+// in `a*` is labelled with `a*`, while the states of `a` itself are labelled with `a`.
+
+pub(crate) fn _expr(pattern: &str, offset: usize, alphabet: &HashSet<Symbol>) -> Nfa {
+    let tokens: Vec<String> = _tokenize_root_level(pattern, '|');
+    let mut nfa = _term(&tokens[0], offset, alphabet);
+    // every token is followed by a single byte '|'
+    let mut start = offset + tokens[0].len() + 1;
     for token in &tokens[1..] {
-        nfa.union(_disjunct(token));
+        nfa.union(_term(token, start, alphabet));
+        start += token.len() + 1;
     }
+    _label(&mut nfa, offset, pattern);
     nfa
 }
 
-fn _disjunct(disjunct: &str) -> Nfa {
+fn _term(term: &str, offset: usize, alphabet: &HashSet<Symbol>) -> Nfa {
+    let tokens: Vec<String> = _tokenize_root_level(term, '&');
+    // no need to go through the product construction if there's nothing to intersect
+    if tokens.len() == 1 {
+        return _disjunct(&tokens[0], offset, alphabet);
+    }
+    let mut dfa = Dfa::from(&_disjunct(&tokens[0], offset, alphabet));
+    let mut start = offset + tokens[0].len() + 1;
+    for token in &tokens[1..] {
+        dfa = dfa.intersect(&Dfa::from(&_disjunct(token, start, alphabet)));
+        start += token.len() + 1;
+    }
+    let mut nfa = Nfa::from_dfa(&dfa);
+    _label(&mut nfa, offset, term);
+    nfa
+}
+
+fn _disjunct(disjunct: &str, offset: usize, alphabet: &HashSet<Symbol>) -> Nfa {
     let tokens = _tokenize_disjunct(disjunct);
-    let mut nfa = _factor(&tokens[0]);
+    let mut nfa = _factor(&tokens[0], offset, alphabet);
+    let mut start = offset + tokens[0].len();
     for token in &tokens[1..] {
-        nfa.concat(_factor(token));
+        nfa.concat(_factor(token, start, alphabet));
+        start += token.len();
     }
     nfa
 }
 
-fn _factor(factor: &str) -> Nfa {
+fn _factor(factor: &str, offset: usize, alphabet: &HashSet<Symbol>) -> Nfa {
+    if let Some(complemented) = factor.strip_prefix('~') {
+        if complemented.is_empty() || complemented.starts_with(['*', '+', '?']) {
+            panic!("Nothing to complement")
+        }
+        let dfa = Dfa::from(&_factor(complemented, offset + 1, alphabet)).complement(alphabet);
+        let mut nfa = Nfa::from_dfa(&dfa);
+        _label(&mut nfa, offset, factor);
+        return nfa;
+    }
+
     // aaaaaaaaaaaaaaaaaa every solution to this is so ugly wtf
     let (atom, suffix) = match factor.chars().rev().next() {
         Some('*') | Some('+') | Some('?') if factor.len() > 1 => (
@@ -59,30 +212,43 @@ fn _factor(factor: &str) -> Nfa {
         panic!("Illegal stacking of quantifiers")
     }
 
-    let mut nfa = _atom(atom);
+    let mut nfa = _atom(atom, offset, alphabet);
     if let Some(c) = suffix {
         match c {
             '?' => nfa.optional(),
             _ => nfa.klenee(c == '*'),
         }
+        _label(&mut nfa, offset, factor);
     }
     nfa
 }
 
-fn _atom(atom: &str) -> Nfa {
+fn _atom(atom: &str, offset: usize, alphabet: &HashSet<Symbol>) -> Nfa {
     // TODO: in the future, escape sequences need to be treated as atoms and handled accordingly
     // TODO: (this length check then isn't a reliable check anymore)
-    if atom.len() == 1 {
+    if atom.chars().count() == 1 {
         let symbol = Symbol::CHAR(atom.chars().nth(0).unwrap());
-        return Nfa::from_symbol(&symbol);
+        let mut nfa = Nfa::from_symbol(&symbol);
+        _label(&mut nfa, offset, atom);
+        return nfa;
+    }
+    return _expr(&atom[1..atom.len() - 1], offset + 1, alphabet);
+}
+
+/// Label every state of `nfa` that doesn't have a span yet with the span of `fragment`, which starts at `offset`.
+fn _label(nfa: &mut Nfa, offset: usize, fragment: &str) {
+    for state in &nfa.states {
+        nfa.spans
+            .entry(*state)
+            .or_insert(offset..offset + fragment.len());
     }
-    return _expr(&atom[1..atom.len() - 1]);
 }
 
 ///////////////////////////////////////////////////// TOKENIZATIONS ////////////////////////////////////////////////////
 
+/// Split `pattern` on every occurrence of `separator` that is not nested inside of brackets.
 /// O(n)
-fn _tokenize_expr(pattern: &str) -> Vec<String> {
+pub(crate) fn _tokenize_root_level(pattern: &str, separator: char) -> Vec<String> {
     let mut tokens = vec!["".to_string()];
     // stack to keep track of encountered brackets
     let mut brackets: usize = 0;
@@ -97,8 +263,8 @@ fn _tokenize_expr(pattern: &str) -> Vec<String> {
             }
             brackets -= 1;
         }
-        // '|' actually encountered on root level and not deeper -> new token
-        if c == '|' && brackets == 0 {
+        // separator actually encountered on root level and not deeper -> new token
+        if c == separator && brackets == 0 {
             tokens.push("".to_string());
             continue;
         }
@@ -108,7 +274,7 @@ fn _tokenize_expr(pattern: &str) -> Vec<String> {
 }
 
 /// O(n)
-fn _tokenize_disjunct(pattern: &str) -> Vec<String> {
+pub(crate) fn _tokenize_disjunct(pattern: &str) -> Vec<String> {
     // not allowed - quantifiers always need to reference a valid regular expression
     if pattern.starts_with(|c| ['*', '+', '?'].contains(&c)) {
         panic!("Nothing to quantify")
@@ -118,8 +284,10 @@ fn _tokenize_disjunct(pattern: &str) -> Vec<String> {
     let mut brackets: usize = 0;
 
     for c in pattern.chars() {
-        // no new factor if we're either inside brackets or have a quantifier
-        if brackets != 0 || ['*', '+', '?'].contains(&c) {
+        // a '~' always belongs to the factor following it
+        let complementing = tokens.last().is_some_and(|t| t.ends_with('~'));
+        // no new factor if we're either inside brackets, have a quantifier or are still building a complement
+        if brackets != 0 || complementing || ['*', '+', '?'].contains(&c) {
             let last = tokens.last_mut().unwrap();
             last.push(c);
         } else {
@@ -0,0 +1,132 @@
+use crate::automata::{Dfa, Nfa, Symbol};
+use std::collections::{BTreeMap, BTreeSet};
+
+mod dot;
+mod mermaid;
+mod table;
+
+/// The parts of an automaton every renderer needs, in a deterministic order.
+/// Parallel transitions between two states are merged into a single edge with a class-style label.
+pub(crate) struct Graph {
+    pub states: Vec<usize>,
+    pub q_start: usize,
+    pub q_accepting: BTreeSet<usize>,
+    /// `(from, to) -> label`
+    pub edges: BTreeMap<(usize, usize), String>,
+    /// Additional text to show next to a state, e.g. the `Nfa` states a `Dfa` state stands for or the span of the
+    /// pattern an `Nfa` state was created for
+    pub notes: BTreeMap<usize, String>,
+}
+
+impl Graph {
+    fn new(
+        states: &[usize],
+        q_start: usize,
+        q_accepting: impl IntoIterator<Item = usize>,
+        transitions: impl IntoIterator<Item = (usize, Symbol, usize)>,
+    ) -> Graph {
+        let mut states = states.to_vec();
+        states.sort();
+
+        let mut symbols: BTreeMap<(usize, usize), Vec<Symbol>> = BTreeMap::new();
+        for (from, with, to) in transitions {
+            symbols.entry((from, to)).or_default().push(with);
+        }
+        let edges = symbols
+            .into_iter()
+            .map(|(edge, symbols)| (edge, symbol_class(&symbols)))
+            .collect();
+
+        Graph {
+            states,
+            q_start,
+            q_accepting: q_accepting.into_iter().collect(),
+            edges,
+            notes: BTreeMap::new(),
+        }
+    }
+}
+
+impl From<&Nfa> for Graph {
+    fn from(nfa: &Nfa) -> Graph {
+        let mut graph = Graph::new(
+            &nfa.states,
+            nfa.q_start,
+            nfa.q_accepting.iter().cloned(),
+            nfa.transitions.iter().cloned(),
+        );
+        graph.notes = nfa
+            .spans
+            .iter()
+            .map(|(state, span)| (*state, format!("{}..{}", span.start, span.end)))
+            .collect();
+        graph
+    }
+}
+
+impl From<&Dfa> for Graph {
+    fn from(dfa: &Dfa) -> Graph {
+        let mut graph = Graph::new(
+            dfa.states(),
+            dfa.q_start,
+            dfa.q_accepting().iter().cloned(),
+            dfa.transitions()
+                .iter()
+                .map(|((from, with), to)| (*from, *with, *to)),
+        );
+        graph.notes = dfa
+            .nfa_states()
+            .iter()
+            .map(|(state, nfa_states)| (*state, state_set(nfa_states)))
+            .collect();
+        graph
+    }
+}
+
+/// Write a set of states the way it's written on paper, e.g. `{0,1,5}`
+pub(crate) fn state_set(states: &BTreeSet<usize>) -> String {
+    let states: Vec<String> = states.iter().map(|q| q.to_string()).collect();
+    format!("{{{}}}", states.join(","))
+}
+
+/// Summarize `symbols` the way one would write a character class, e.g. `[a, b, c, d, x]` becomes `a-d,x`.
+/// Epsilon is written as `ε` and always comes last.
+pub(crate) fn symbol_class(symbols: &[Symbol]) -> String {
+    let chars: BTreeSet<char> = symbols
+        .iter()
+        .filter_map(|s| match s {
+            Symbol::CHAR(c) => Some(*c),
+            _ => None,
+        })
+        .collect();
+
+    let mut parts = class_items(&chars);
+    if symbols.contains(&Symbol::EPSILON) {
+        parts.push(String::from("ε"));
+    }
+    parts.join(",")
+}
+
+/// Split `chars` into single chars and ranges of at least three consecutive chars, e.g. `abcdx` becomes
+/// `["a-d", "x"]`.
+pub(crate) fn class_items(chars: &BTreeSet<char>) -> Vec<String> {
+    // collect runs of consecutive chars as inclusive ranges
+    let mut ranges: Vec<(char, char)> = Vec::new();
+    for c in chars {
+        match ranges.last_mut() {
+            Some((_, end)) if (*end as u32) + 1 == *c as u32 => *end = *c,
+            _ => ranges.push((*c, *c)),
+        }
+    }
+
+    let mut items: Vec<String> = Vec::new();
+    for (start, end) in ranges {
+        match end as u32 - start as u32 {
+            0 => items.push(start.to_string()),
+            // a range of two isn't any shorter than listing both
+            1 => items.extend([start.to_string(), end.to_string()]),
+            _ => items.push(format!("{}-{}", start, end)),
+        }
+    }
+    items
+}
@@ -0,0 +1,79 @@
+use crate::automata::{Dfa, Nfa};
+use crate::render::Graph;
+use std::fmt::Write;
+
+/////////////////////////////////////////////////////// GRAPHVIZ ///////////////////////////////////////////////////////
+
+impl Nfa {
+    /// Render `self` in the Graphviz DOT language, e.g. to pipe it into `dot -Tsvg`.
+    pub fn to_dot(&self) -> String {
+        to_dot("Nfa", &Graph::from(self))
+    }
+
+    /// Like `to_dot`, but label every state with the fragment of `pattern` it was created for instead of its span.
+    /// `pattern` is expected to be the one `self` was parsed from.
+    pub fn to_dot_with_pattern(&self, pattern: &str) -> String {
+        let mut graph = Graph::from(self);
+        for (state, span) in &self.spans {
+            if let Some(fragment) = pattern.get(span.clone()) {
+                graph.notes.insert(*state, String::from(fragment));
+            }
+        }
+        to_dot("Nfa", &graph)
+    }
+}
+
+impl Dfa {
+    /// Render `self` in the Graphviz DOT language, e.g. to pipe it into `dot -Tsvg`.
+    pub fn to_dot(&self) -> String {
+        to_dot("Dfa", &Graph::from(self))
+    }
+}
+
+fn to_dot(name: &str, graph: &Graph) -> String {
+    // writing into a String can't fail, hence all the unwraps
+    let mut dot = String::new();
+    writeln!(dot, "digraph {} {{", name).unwrap();
+    writeln!(dot, "\trankdir=LR;").unwrap();
+    // invisible node for the start arrow to come from
+    writeln!(dot, "\tstart [shape=point, style=invis];").unwrap();
+    for state in &graph.states {
+        let shape = if graph.q_accepting.contains(state) {
+            "doublecircle"
+        } else {
+            "circle"
+        };
+        match graph.notes.get(state) {
+            Some(note) => writeln!(
+                dot,
+                "\tq{} [shape={}, label=\"{}\", xlabel=\"{}\"];",
+                state,
+                shape,
+                state,
+                escape(note)
+            ),
+            None => writeln!(dot, "\tq{} [shape={}, label=\"{}\"];", state, shape, state),
+        }
+        .unwrap();
+    }
+    writeln!(dot, "\tstart -> q{};", graph.q_start).unwrap();
+    for ((from, to), label) in &graph.edges {
+        writeln!(dot, "\tq{} -> q{} [label=\"{}\"];", from, to, escape(label)).unwrap();
+    }
+    write!(dot, "}}").unwrap();
+    dot
+}
+
+/// Escape `label` to be put into a double-quoted DOT string
+fn escape(label: &str) -> String {
+    let mut escaped = String::with_capacity(label.len());
+    for c in label.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
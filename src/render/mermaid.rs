@@ -0,0 +1,41 @@
+use crate::automata::Dfa;
+use crate::render::Graph;
+use std::fmt::Write;
+
+/////////////////////////////////////////////////////// MERMAID ////////////////////////////////////////////////////////
+
+impl Dfa {
+    /// Render `self` as a Mermaid `stateDiagram-v2`.
+    /// Mermaid has no notion of accepting states, so they get an edge to the end state `[*]` instead.
+    pub fn to_mermaid(&self) -> String {
+        let graph = Graph::from(self);
+        // writing into a String can't fail, hence all the unwraps
+        let mut mermaid = String::new();
+        writeln!(mermaid, "stateDiagram-v2").unwrap();
+        writeln!(mermaid, "\tdirection LR").unwrap();
+        for state in &graph.states {
+            writeln!(mermaid, "\tq{} : {}", state, state).unwrap();
+        }
+        writeln!(mermaid, "\t[*] --> q{}", graph.q_start).unwrap();
+        for ((from, to), label) in &graph.edges {
+            writeln!(mermaid, "\tq{} --> q{} : {}", from, to, escape(label)).unwrap();
+        }
+        for state in &graph.q_accepting {
+            writeln!(mermaid, "\tq{} --> [*]", state).unwrap();
+        }
+        mermaid.pop(); // trailing newline
+        mermaid
+    }
+}
+
+/// Replace everything that would end a Mermaid label early with its entity code
+fn escape(label: &str) -> String {
+    let mut escaped = String::with_capacity(label.len());
+    for c in label.chars() {
+        match c {
+            '#' | ':' | ';' | '"' | '<' | '>' | '\n' => write!(escaped, "#{};", c as u32).unwrap(),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
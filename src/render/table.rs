@@ -0,0 +1,100 @@
+use crate::automata::{Dfa, Symbol};
+use crate::render::{state_set, symbol_class};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+//////////////////////////////////////////////////// TRANSITION TABLE //////////////////////////////////////////////////
+
+impl Dfa {
+    /// Render the transition table of `self` as plain text, one row per state and one column per symbol class.
+    /// Symbols behaving identically in every state share a column, so `[a-z]` doesn't produce 26 columns.
+    /// The start state is marked with `->`, accepting states with `*`, missing transitions are written as `-`.
+    /// If known, the `Nfa` states each state stands for are listed in an additional last column.
+    ///
+    /// ```text
+    ///       | a-c | x
+    /// ->  0 | 1   | -
+    ///   * 1 | 1   | 0
+    /// ```
+    pub fn to_table(&self) -> String {
+        let mut states = self.states().clone();
+        states.sort();
+
+        // group symbols by their column, i.e. the targets they lead to from every state
+        let mut classes: BTreeMap<Vec<Option<usize>>, Vec<Symbol>> = BTreeMap::new();
+        for symbol in self.alphabet() {
+            let column = states
+                .iter()
+                .map(|q| self.transitions().get(&(*q, *symbol)).cloned())
+                .collect();
+            classes.entry(column).or_default().push(*symbol);
+        }
+        let mut columns: Vec<(Vec<Symbol>, Vec<Option<usize>>)> = classes
+            .into_iter()
+            .map(|(column, symbols)| (symbols, column))
+            .collect();
+        // order columns by their smallest symbol, a dfa only ever has transitions for actual chars
+        columns.sort_by_key(|(symbols, _)| {
+            symbols
+                .iter()
+                .filter_map(|s| match s {
+                    Symbol::CHAR(c) => Some(*c),
+                    _ => None,
+                })
+                .min()
+        });
+
+        let mut rows: Vec<Vec<String>> = Vec::with_capacity(states.len() + 1);
+        let mut header = vec![String::new()];
+        header.extend(columns.iter().map(|(symbols, _)| symbol_class(symbols)));
+        let nfa_states = !self.nfa_states().is_empty();
+        if nfa_states {
+            header.push(String::from("Nfa states"));
+        }
+        rows.push(header);
+        for (i, state) in states.iter().enumerate() {
+            let start = if *state == self.q_start { "->" } else { "  " };
+            let accepting = if self.q_accepting().contains(state) {
+                "*"
+            } else {
+                " "
+            };
+            let mut row = vec![format!("{}{} {}", start, accepting, state)];
+            row.extend(columns.iter().map(|(_, column)| match column[i] {
+                Some(to) => to.to_string(),
+                None => String::from("-"),
+            }));
+            if nfa_states {
+                row.push(
+                    self.nfa_states()
+                        .get(state)
+                        .map(state_set)
+                        .unwrap_or_default(),
+                );
+            }
+            rows.push(row);
+        }
+
+        let widths: Vec<usize> = (0..rows[0].len())
+            .map(|i| rows.iter().map(|row| row[i].chars().count()).max().unwrap())
+            .collect();
+        rows.iter()
+            .map(|row| {
+                row.iter()
+                    .zip(&widths)
+                    .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                    .collect::<Vec<String>>()
+                    .join(" | ")
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl Display for Dfa {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_table())
+    }
+}
@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod test_ambiguity {
+    use crate::automata::Automaton;
+    use crate::automata::ambiguity::{Ambiguity, Attack};
+    use crate::parse::parse;
+    use crate::test::dfa;
+
+    fn ambiguity(pattern: &str) -> Ambiguity {
+        parse(pattern).ambiguity()
+    }
+
+    /// The attack on `pattern`, after making sure its words are rejected
+    fn attack(pattern: &str) -> Attack {
+        let attack = match ambiguity(pattern) {
+            Ambiguity::Bounded => panic!("{} is not ambiguous", pattern),
+            Ambiguity::Polynomial { attack, .. } | Ambiguity::Exponential { attack } => attack,
+        };
+        let dfa = dfa(pattern);
+        for n in 0..10 {
+            assert!(
+                !dfa.accept(&attack.word(n)),
+                "{} {}",
+                pattern,
+                attack.word(n)
+            );
+        }
+        attack
+    }
+
+    #[test]
+    fn test_bounded() {
+        for pattern in [
+            "a",
+            "a*",
+            "a*b*",
+            "(ab|a)*",
+            "x(a|ab)*y",
+            "a*ba*",
+            "(a?)*",
+            "~(a*)&(a|b)*",
+        ] {
+            assert_eq!(ambiguity(pattern), Ambiguity::Bounded, "{}", pattern);
+        }
+    }
+
+    #[test]
+    fn test_exponential() {
+        for pattern in [
+            "(a|a)*",
+            "(a+)+",
+            "(a*)*",
+            "(aa|a)*",
+            "((a|b)*)*c",
+            "x(a|b)*c(a*)*",
+        ] {
+            assert!(
+                matches!(ambiguity(pattern), Ambiguity::Exponential { .. }),
+                "{}",
+                pattern
+            );
+            attack(pattern);
+        }
+        assert_eq!(
+            attack("(a+)+"),
+            Attack {
+                prefix: String::from("a"),
+                pumps: vec![(String::from("a"), String::new())],
+                suffix: String::from("!"),
+            }
+        );
+        // the pattern needs a "c" at the end, so leaving it out already fails
+        assert_eq!(attack("((a|b)*)*c").suffix, "");
+    }
+
+    #[test]
+    fn test_polynomial() {
+        let degree = |pattern: &str| match ambiguity(pattern) {
+            Ambiguity::Polynomial { degree, .. } => degree,
+            other => panic!("{} is {:?}", pattern, other),
+        };
+        assert_eq!(degree("a*a*"), 1);
+        assert_eq!(degree("(a|b)*b(a|b)*"), 1);
+        assert_eq!(degree("a*b?a*"), 1);
+        assert_eq!(degree("a*a*a*"), 2);
+        assert_eq!(degree("a*a*a*a*"), 3);
+        // the IDA of the a*s can't be chained with one of the x*s, as neither is reachable from the other: every IDA of
+        // the x*s starts in front of the second a* and ends behind the first one
+        assert_eq!(degree("x*a*x*a*x*"), 2);
+
+        let chained = attack("a*a*a*");
+        assert_eq!(chained.pumps.len(), 2);
+        assert_eq!(chained.word(3), "aaaaaaa!");
+        assert_eq!(chained.to_string(), "\"a\" + n * \"a\" + n * \"a\" + \"!\"");
+        attack("x*a*x*a*x*");
+    }
+}
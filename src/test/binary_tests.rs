@@ -0,0 +1,235 @@
+#[cfg(test)]
+mod test_binary_format {
+    use crate::automata::binary::crc32;
+    use crate::automata::{Automaton, Dfa};
+    use crate::test::dfa;
+
+    /// Overwrite the checksum of `bytes` so that tampering goes unnoticed
+    fn fix_checksum(bytes: &mut [u8]) {
+        let at = bytes.len() - 4;
+        let checksum = crc32(&bytes[..at]);
+        bytes[at..].copy_from_slice(&checksum.to_le_bytes());
+    }
+
+    #[test]
+    fn test_crc32() {
+        // the check value from the crc catalogue
+        assert_eq!(crc32(b"123456789"), 0xCBF43926);
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let original = dfa("a?b+(a|c)?|c+");
+        let bytes = original.to_bytes();
+        let loaded = Dfa::from_bytes(&bytes).unwrap();
+        println!("{:?}", loaded);
+        assert!(loaded.validate().is_ok());
+        assert_eq!(loaded.states().len(), original.states().len());
+        assert_eq!(loaded.alphabet(), original.alphabet());
+        for word in original.words(Some(5)) {
+            assert!(loaded.accept(&word), "{}", word);
+        }
+        assert_eq!(loaded.count_up_to(8), original.count_up_to(8));
+        // reserializing yields the very same bytes
+        assert_eq!(loaded.to_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_roundtrip_gets_fresh_states() {
+        let original = dfa("ab");
+        let loaded = Dfa::from_bytes(&original.to_bytes()).unwrap();
+        assert!(
+            loaded
+                .states()
+                .iter()
+                .all(|q| !original.states().contains(q))
+        );
+    }
+
+    #[test]
+    fn test_header() {
+        let bytes = dfa("ab|c").to_bytes();
+        assert_eq!(&bytes[0..4], b"RDFA");
+        assert_eq!(&bytes[4..6], &[1, 0]);
+        // 3 symbols
+        assert_eq!(&bytes[12..16], &[3, 0, 0, 0]);
+        assert_eq!(&bytes[20..24], &[b'a', 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_rejects_wrong_magic() {
+        let mut bytes = dfa("a").to_bytes();
+        bytes[0] = b'X';
+        assert_eq!(Dfa::from_bytes(&bytes).unwrap_err(), "Not a serialized Dfa");
+        assert!(Dfa::from_bytes(&[]).is_err());
+    }
+
+    #[test]
+    fn test_rejects_unknown_version() {
+        let mut bytes = dfa("a").to_bytes();
+        bytes[4] = 2;
+        assert!(Dfa::from_bytes(&bytes).unwrap_err().contains("version 2"));
+    }
+
+    #[test]
+    fn test_rejects_reserved_bytes() {
+        let mut bytes = dfa("a").to_bytes();
+        bytes[7] = 1;
+        fix_checksum(&mut bytes);
+        assert_eq!(
+            Dfa::from_bytes(&bytes).unwrap_err(),
+            "Reserved header bytes are not 0"
+        );
+    }
+
+    #[test]
+    fn test_rejects_truncated() {
+        let bytes = dfa("abc").to_bytes();
+        assert!(Dfa::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_rejects_corruption() {
+        let mut bytes = dfa("abc").to_bytes();
+        let last = bytes.len() - 5;
+        bytes[last] ^= 1;
+        assert_eq!(Dfa::from_bytes(&bytes).unwrap_err(), "Checksum mismatch");
+    }
+
+    #[test]
+    fn test_rejects_invalid_contents() {
+        let original = dfa("ab").to_bytes();
+
+        let mut bytes = original.clone();
+        // start state out of bounds
+        bytes[16] = 200;
+        fix_checksum(&mut bytes);
+        assert_eq!(Dfa::from_bytes(&bytes).unwrap_err(), "q_0 ∉ Q");
+
+        let mut bytes = original.clone();
+        // first transition of the table points nowhere
+        let table_at = bytes.len() - 4 - 4 * 2 * 3;
+        bytes[table_at] = 100;
+        bytes[table_at + 1] = 0;
+        bytes[table_at + 2] = 0;
+        bytes[table_at + 3] = 0;
+        fix_checksum(&mut bytes);
+        assert!(
+            Dfa::from_bytes(&bytes)
+                .unwrap_err()
+                .contains("unknown state")
+        );
+
+        let mut bytes = original;
+        // symbols out of order
+        bytes.swap(20, 24);
+        fix_checksum(&mut bytes);
+        assert!(
+            Dfa::from_bytes(&bytes)
+                .unwrap_err()
+                .contains("unordered symbol")
+        );
+    }
+
+    #[test]
+    fn test_rejects_absurd_sizes_without_allocating() {
+        let mut bytes = dfa("a").to_bytes();
+        bytes[8..16].copy_from_slice(&[0xFF; 8]);
+        fix_checksum(&mut bytes);
+        assert!(Dfa::from_bytes(&bytes).is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_zero_copy {
+    use crate::automata::binary::DfaRef;
+    use crate::automata::{Automaton, Dfa};
+    use crate::parse::parse;
+    use crate::test::dfa;
+
+    #[test]
+    fn test_accept_agrees_with_dfa() {
+        let dfa = dfa("a?b+(a|c)?|c+");
+        let bytes = dfa.to_bytes();
+        let dfa_ref = DfaRef::new(&bytes).unwrap();
+        for word in ["b", "aba", "ba", "c", "ccccc", "bc", "", "aab", "ac", "xyz"] {
+            assert_eq!(dfa_ref.accept(word), dfa.accept(word), "{}", word);
+        }
+    }
+
+    #[test]
+    fn test_find_agrees_with_dfa() {
+        let pattern = "a?b+(a|c)?|c+";
+        let nfa = parse(pattern);
+        let dfa = Dfa::from(&nfa);
+        let dfa_reversed = Dfa::from(&nfa.reversed().to_finding());
+        let (bytes, bytes_reversed) = (dfa.to_bytes(), dfa_reversed.to_bytes());
+        let dfa_ref = DfaRef::new(&bytes).unwrap();
+        let dfa_ref_reversed = DfaRef::new(&bytes_reversed).unwrap();
+
+        for input in [
+            "aab",
+            "aabbaa",
+            "cccba",
+            "acba",
+            "bbbccc",
+            "the bbc is the british broadcasting network",
+            "aa",
+        ] {
+            let input_reversed: String = input.chars().rev().collect();
+            assert_eq!(
+                dfa_ref_reversed.find_all(&input_reversed, &dfa_ref),
+                dfa_reversed.find_all(&input_reversed, &dfa),
+                "{}",
+                input
+            );
+        }
+        // "aab" reversed
+        assert_eq!(dfa_ref_reversed.find("baa", &dfa_ref), Some((1, 2)));
+    }
+
+    #[test]
+    fn test_to_dfa() {
+        let bytes = dfa("ab*").to_bytes();
+        let dfa = DfaRef::new(&bytes).unwrap().to_dfa().unwrap();
+        assert!(dfa.accept("abbb"));
+        assert!(!dfa.accept("b"));
+    }
+
+    #[test]
+    fn test_rejects_invalid_bytes() {
+        let mut bytes = dfa("ab").to_bytes();
+        bytes[10] ^= 0xFF;
+        assert!(DfaRef::new(&bytes).is_err());
+        assert!(DfaRef::new(b"RDFA").is_err());
+    }
+
+    #[test]
+    fn test_unchecked() {
+        let mut bytes = dfa("ab").to_bytes();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xFF;
+        let dfa_ref = DfaRef::new_unchecked(&bytes).unwrap();
+        assert!(dfa_ref.accept("ab"));
+        assert_eq!(dfa_ref.verify().unwrap_err(), "Checksum mismatch");
+        assert_eq!(dfa_ref.to_dfa().unwrap_err(), "Checksum mismatch");
+        assert!(DfaRef::new(&bytes).is_err());
+
+        // the header is still checked
+        assert!(DfaRef::new_unchecked(&bytes[..bytes.len() - 1]).is_err());
+        assert!(DfaRef::new_unchecked(b"RDFA").is_err());
+    }
+
+    #[test]
+    fn test_unchecked_never_panics() {
+        let mut bytes = dfa("ab").to_bytes();
+        // every transition points nowhere
+        let table_at = bytes.len() - 4 - 4 * 2 * 3;
+        let end = bytes.len() - 4;
+        bytes[table_at..end].fill(0x7F);
+        let dfa_ref = DfaRef::new_unchecked(&bytes).unwrap();
+        assert!(!dfa_ref.accept("ab"));
+        assert!(dfa_ref.verify().is_err());
+    }
+}
@@ -0,0 +1,106 @@
+#[cfg(test)]
+mod test_boolean_operations {
+    use crate::automata::{Automaton, Symbol};
+    use crate::test::dfa;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_totalize() {
+        let dfa = dfa("ab");
+        let total = dfa.totalize(&HashSet::from([Symbol::CHAR('c')]));
+        println!("{:?}", total);
+        assert!(total.validate().is_ok());
+        assert!(total.accept("ab"));
+        assert!(!total.accept("abc"));
+        assert!(!total.accept("c"));
+        assert!(!total.accept(""));
+    }
+
+    #[test]
+    fn test_complement() {
+        let dfa = dfa("a*");
+        let complement = dfa.complement(&HashSet::from([Symbol::CHAR('b')]));
+        println!("{:?}", complement);
+        assert!(complement.validate().is_ok());
+        assert!(complement.accept("b"));
+        assert!(complement.accept("ab"));
+        assert!(complement.accept("aaba"));
+        // not matching
+        assert!(!complement.accept(""));
+        assert!(!complement.accept("a"));
+        assert!(!complement.accept("aaaa"));
+        // symbols outside of the alphabet are still rejected
+        assert!(!complement.accept("c"));
+    }
+
+    #[test]
+    fn test_complement_without_additional_alphabet() {
+        let complement = dfa("ab").complement(&HashSet::new());
+        assert!(complement.validate().is_ok());
+        assert!(complement.accept(""));
+        assert!(complement.accept("a"));
+        assert!(complement.accept("ba"));
+        assert!(complement.accept("abab"));
+        assert!(!complement.accept("ab"));
+    }
+
+    #[test]
+    fn test_intersect() {
+        // contains an a, but ends with b
+        let intersection = dfa("(a|b)*a(a|b)*").intersect(&dfa("(a|b)*b"));
+        println!("{:?}", intersection);
+        assert!(intersection.validate().is_ok());
+        assert!(intersection.accept("ab"));
+        assert!(intersection.accept("bab"));
+        assert!(intersection.accept("aaabbb"));
+        // not matching
+        assert!(!intersection.accept(""));
+        assert!(!intersection.accept("b"));
+        assert!(!intersection.accept("bbb"));
+        assert!(!intersection.accept("aba"));
+    }
+
+    #[test]
+    fn test_intersect_disjoint_alphabets() {
+        let intersection = dfa("a*").intersect(&dfa("b*"));
+        assert!(intersection.validate().is_ok());
+        assert!(intersection.accept(""));
+        assert!(!intersection.accept("a"));
+        assert!(!intersection.accept("b"));
+    }
+
+    #[test]
+    fn test_difference() {
+        let difference = dfa("a*").difference(&dfa("aa"));
+        println!("{:?}", difference);
+        assert!(difference.validate().is_ok());
+        assert!(difference.accept(""));
+        assert!(difference.accept("a"));
+        assert!(difference.accept("aaa"));
+        // not matching
+        assert!(!difference.accept("aa"));
+        assert!(!difference.accept("b"));
+    }
+
+    #[test]
+    fn test_symmetric_difference() {
+        let symmetric_difference = dfa("a|b").symmetric_difference(&dfa("b|c"));
+        println!("{:?}", symmetric_difference);
+        assert!(symmetric_difference.validate().is_ok());
+        assert!(symmetric_difference.accept("a"));
+        assert!(symmetric_difference.accept("c"));
+        // not matching
+        assert!(!symmetric_difference.accept("b"));
+        assert!(!symmetric_difference.accept(""));
+        assert!(!symmetric_difference.accept("ab"));
+    }
+
+    #[test]
+    fn test_symmetric_difference_of_equivalent_patterns() {
+        let symmetric_difference = dfa("a*").symmetric_difference(&dfa("(a|aa)*"));
+        assert!(symmetric_difference.validate().is_ok());
+        for word in ["", "a", "aa", "aaa", "b"] {
+            assert!(!symmetric_difference.accept(word));
+        }
+    }
+}
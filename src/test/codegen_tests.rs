@@ -0,0 +1,262 @@
+#[cfg(test)]
+mod test_rust_codegen {
+    use crate::automata::{Automaton, Dfa};
+    use crate::codegen::rust::RustStyle;
+    use crate::parse::parse;
+    use crate::test::dfa;
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    const PATTERNS: [&str; 4] = ["a?b+(a|c)?|c+", "(ab|cd)*e", "abc|abd", "x(y|z)*"];
+    const INPUTS: [&str; 10] = [
+        "", "b", "abba", "ccc", "abcde", "e", "cdabe", "zabd", "xyzzy", "ab\n'\\c",
+    ];
+
+    /// Compile `code` with the rustc that's running the tests and run it, panicking if either fails
+    fn compile_and_run(name: &str, code: &str) {
+        let dir = std::env::temp_dir().join(format!("rusty-dfa-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("main.rs");
+        let binary: PathBuf = dir.join("main");
+        std::fs::write(&source, code).unwrap();
+
+        let rustc = std::env::var("RUSTC").unwrap_or(String::from("rustc"));
+        let output = Command::new(rustc)
+            .args(["--edition", "2021", "-O", "-o"])
+            .arg(&binary)
+            .arg(&source)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}\n{}",
+            String::from_utf8_lossy(&output.stderr),
+            code
+        );
+        let output = Command::new(&binary).output().unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_match_style() {
+        let dfa = Dfa::from_json(
+            r#"{ "type": "dfa", "states": [0, 1, 2], "start": 0, "accepting": [2],
+                 "transitions": [{ "from": 0, "to": 1, "class": ["b-e", "x"] }, { "from": 1, "to": 2, "symbol": "y" }] }"#,
+        )
+        .unwrap();
+        let code = dfa.to_rust("matcher", RustStyle::Match);
+        assert!(code.starts_with("// Generated by rusty-dfa"));
+        assert!(code.contains("pub mod matcher {"));
+        assert!(code.contains("'b'..='e' => Some(1),"));
+        assert!(code.contains("'x' => Some(1),"));
+        assert!(code.contains("matches!(state, 2)"));
+        assert!(code.contains("pub fn is_match(input: &str) -> bool"));
+        assert!(code.contains("pub fn find(input: &str) -> Option<(usize, usize)>"));
+    }
+
+    #[test]
+    fn test_table_style() {
+        let code = dfa("ab*").to_rust("matcher", RustStyle::Table);
+        assert!(code.contains("static SYMBOLS: [char; 2] = ['a', 'b'];"));
+        assert!(code.contains("static TABLE: [[u32; 2]; "));
+        assert!(!code.contains("match c"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_name() {
+        dfa("a").to_rust("match", RustStyle::Match);
+    }
+
+    #[test]
+    fn test_generated_code_agrees() {
+        let mut code = String::new();
+        let mut checks = String::new();
+        for (i, pattern) in PATTERNS.iter().enumerate() {
+            let nfa = parse(pattern);
+            let dfa = Dfa::from(&nfa);
+            for (style, name) in [(RustStyle::Match, "m"), (RustStyle::Table, "t")] {
+                let module = format!("{}{}", name, i);
+                code.push_str(&dfa.to_rust(&module, style));
+                for input in INPUTS {
+                    checks.push_str(&format!(
+                        "    assert_eq!({}::is_match({:?}), {}, {:?});\n",
+                        module,
+                        input,
+                        dfa.accept(input),
+                        input
+                    ));
+                    checks.push_str(&format!(
+                        "    assert_eq!({}::find({:?}), {:?}, {:?});\n",
+                        module,
+                        input,
+                        nfa.find(input),
+                        input
+                    ));
+                }
+            }
+        }
+        code.push_str(&format!("\nfn main() {{\n{}}}\n", checks));
+        compile_and_run("rust-codegen", &code);
+    }
+
+    #[test]
+    fn test_empty_language() {
+        let code = dfa("a&b").to_rust("empty", RustStyle::Match);
+        compile_and_run(
+            "rust-codegen-empty",
+            &format!(
+                "{}\nfn main() {{ assert!(!empty::is_match(\"a\")); assert_eq!(empty::find(\"ab\"), None); }}\n",
+                code
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_c_codegen {
+    use crate::automata::{Automaton, find_leftmost_longest};
+    use crate::test::dfa;
+    use std::process::Command;
+
+    const PATTERNS: [&str; 4] = [
+        "a?b+(a|c)?|c+",
+        "(ab|cd)*e",
+        "x(y|ä|€)*z",
+        "~(a|b)*&(a|b|c)(a|b|c)",
+    ];
+    const INPUTS: [&str; 12] = [
+        "", "b", "abba", "ccc", "abcde", "e", "cdabe", "xz", "xäy€z", "--xäz--", "ac", "ab",
+    ];
+
+    /// `input` as C string literal, octal escapes can't run into the following char unlike hex ones
+    fn c_string(input: &str) -> String {
+        let escaped: String = input.bytes().map(|b| format!("\\{:03o}", b)).collect();
+        format!("\"{}\"", escaped)
+    }
+
+    /// Compile the `(header, source)` of every matcher together with `main` using the system C compiler and run it
+    fn compile_and_run(name: &str, matchers: &[(String, (String, String))], main: &str) {
+        let dir = std::env::temp_dir().join(format!("rusty-dfa-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut sources = vec![dir.join("main.c")];
+        std::fs::write(&sources[0], main).unwrap();
+        for (matcher, (header, source)) in matchers {
+            std::fs::write(dir.join(format!("{}.h", matcher)), header).unwrap();
+            sources.push(dir.join(format!("{}.c", matcher)));
+            std::fs::write(sources.last().unwrap(), source).unwrap();
+        }
+
+        let cc = std::env::var("CC").unwrap_or(String::from("cc"));
+        let binary = dir.join("main");
+        let output = Command::new(cc)
+            .args(["-std=c99", "-Wall", "-Wextra", "-pedantic", "-Werror", "-o"])
+            .arg(&binary)
+            .args(&sources)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let output = Command::new(&binary).output().unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_header_and_source() {
+        let (header, source) = dfa("ab*").to_c("ab_star");
+        assert!(header.contains("#ifndef AB_STAR_H"));
+        assert!(header.contains("int ab_star_match(const unsigned char *input, size_t len);"));
+        assert!(header.contains(
+            "int ab_star_find(const unsigned char *input, size_t len, size_t *start, size_t *end);"
+        ));
+        assert!(source.contains("#include \"ab_star.h\""));
+        assert!(source.contains("typedef uint8_t state_t;"));
+        assert!(source.contains("[0x61] = "));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_name() {
+        dfa("a").to_c("not-an-identifier");
+    }
+
+    #[test]
+    fn test_generated_code_agrees() {
+        let mut matchers = Vec::new();
+        let mut checks = String::new();
+        for (i, pattern) in PATTERNS.iter().enumerate() {
+            let dfa = dfa(pattern);
+            let name = format!("m{}", i);
+            for input in INPUTS {
+                let len = input.len();
+                checks.push_str(&format!(
+                    "    if ({}_match((const unsigned char *){}, {}) != {}) fail({:?}, {:?});\n",
+                    name,
+                    c_string(input),
+                    len,
+                    dfa.accept(input) as u8,
+                    pattern,
+                    input
+                ));
+                // char indices with an inclusive end to byte offsets with an exclusive one
+                let expected = find_leftmost_longest(&dfa, input).map(|(start, end)| {
+                    let offsets: Vec<usize> = input
+                        .char_indices()
+                        .map(|(at, _)| at)
+                        .chain([len])
+                        .collect();
+                    (offsets[start], offsets[end + 1])
+                });
+                checks.push_str(&match expected {
+                    Some((start, end)) => format!(
+                        "    if (!{}_find((const unsigned char *){}, {}, &start, &end) || start != {} || end != {}) fail({:?}, {:?});\n",
+                        name, c_string(input), len, start, end, pattern, input
+                    ),
+                    None => format!(
+                        "    if ({}_find((const unsigned char *){}, {}, &start, &end)) fail({:?}, {:?});\n",
+                        name, c_string(input), len, pattern, input
+                    ),
+                });
+            }
+            matchers.push((name.clone(), dfa.to_c(&name)));
+        }
+
+        let includes: String = matchers
+            .iter()
+            .map(|(name, _)| format!("#include \"{}.h\"\n", name))
+            .collect();
+        let main = format!(
+            "#include <stdio.h>
+{}
+static int failures = 0;
+
+static void fail(const char *pattern, const char *input)
+{{
+    fprintf(stderr, \"mismatch for %s on %s\\n\", pattern, input);
+    failures++;
+}}
+
+int main(void)
+{{
+    size_t start, end;
+{}    return failures != 0;
+}}
+",
+            includes, checks
+        );
+        compile_and_run("c-codegen", &matchers, &main);
+    }
+}
@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod test_word_counting {
+    use crate::test::dfa;
+
+    #[test]
+    fn test_count_words() {
+        let dfa = dfa("(a|b)*");
+        assert_eq!(dfa.count_words(0), 1);
+        assert_eq!(dfa.count_words(1), 2);
+        assert_eq!(dfa.count_words(10), 1024);
+    }
+
+    #[test]
+    fn test_count_words_of_finite_language() {
+        let dfa = dfa("a|bc|de|fgh");
+        assert_eq!(dfa.count_words(0), 0);
+        assert_eq!(dfa.count_words(1), 1);
+        assert_eq!(dfa.count_words(2), 2);
+        assert_eq!(dfa.count_words(3), 1);
+        assert_eq!(dfa.count_words(4), 0);
+    }
+
+    #[test]
+    fn test_count_words_does_not_count_duplicates() {
+        // ambiguous pattern, but every word must only be counted once
+        let dfa = dfa("(a|aa)*");
+        assert_eq!(dfa.count_words(3), 1);
+        assert_eq!(dfa.count_up_to(3), 4);
+    }
+
+    #[test]
+    fn test_count_up_to() {
+        let dfa = dfa("(a|b)*");
+        assert_eq!(dfa.count_up_to(0), 1);
+        assert_eq!(dfa.count_up_to(3), 1 + 2 + 4 + 8);
+    }
+
+    #[test]
+    fn test_count_saturates() {
+        let dfa = dfa("(a|b|c|d)*");
+        // 4^64 = 2^128 doesn't fit anymore
+        assert_eq!(dfa.count_words(63), 1 << 126);
+        assert_eq!(dfa.count_words(64), u128::MAX);
+        assert_eq!(dfa.count_up_to(100), u128::MAX);
+    }
+
+    #[test]
+    fn test_cardinality() {
+        assert_eq!(dfa("a|bc|de|fgh").cardinality(), Some(4));
+        assert_eq!(dfa("(a|b)(a|b)?(a|b)?").cardinality(), Some(2 + 4 + 8));
+        assert_eq!(dfa("a?").cardinality(), Some(2));
+        assert_eq!(dfa("a&b").cardinality(), Some(0));
+        assert_eq!(dfa("a*").cardinality(), None);
+        assert_eq!(dfa("ab+").cardinality(), None);
+    }
+}
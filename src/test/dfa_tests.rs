@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod test_powerset_construction {
-    use std::collections::{HashMap, HashSet};
+    use std::collections::{BTreeSet, HashMap, HashSet};
     use crate::automata::{Automaton, Dfa, Nfa, Symbol};
     use crate::parse::parse;
     
@@ -62,6 +62,54 @@ mod test_powerset_construction {
         assert!(!dfa.accept("aaab"));
     }
 
+    #[test]
+    fn from_reversed_nfa() {
+        // `Nfa::reversed` inserts its transitions by hand, without updating the alphabet
+        let nfa = parse("ab|c").reversed();
+        let dfa = Dfa::from(&nfa);
+        println!("{:?}", dfa);
+        assert!(dfa.validate().is_ok());
+        assert!(dfa.accept("ba"));
+        assert!(dfa.accept("c"));
+        assert!(!dfa.accept("ab"));
+    }
+
+    #[test]
+    fn test_nfa_states() {
+        let nfa = parse("a(b|c)*");
+        assert!(Dfa::from(&nfa).nfa_states().is_empty());
+        let dfa = Dfa::from_with_provenance(&nfa);
+        assert_eq!(dfa.nfa_states().len(), dfa.states().len());
+        let start: BTreeSet<usize> = nfa.ec(nfa.q_start).into_iter().collect();
+        assert_eq!(dfa.nfa_states()[&dfa.q_start], start);
+        // a state is accepting iff it stands for an accepting state of the nfa
+        for (state, nfa_states) in dfa.nfa_states() {
+            assert_eq!(
+                dfa.q_accepting().contains(state),
+                nfa_states.iter().any(|q| nfa.q_accepting.contains(q))
+            );
+        }
+        assert!(dfa.without_nfa_states().nfa_states().is_empty());
+    }
+
+    #[test]
+    fn test_nfa_states_are_carried_over() {
+        let dfa = Dfa::from_with_provenance(&parse("a(b|c)*"));
+        // all accepting states are merged into one
+        let minimal = dfa.minimize();
+        let merged: BTreeSet<usize> = dfa
+            .q_accepting()
+            .iter()
+            .flat_map(|q| dfa.nfa_states()[q].clone())
+            .collect();
+        let accepting = minimal.q_accepting().iter().next().unwrap();
+        assert_eq!(minimal.nfa_states()[accepting], merged);
+
+        let total = dfa.totalize(&HashSet::from([Symbol::CHAR('d')]));
+        assert_eq!(total.nfa_states(), dfa.nfa_states());
+        // a product state stands for two sets of two different nfas
+        assert!(dfa.intersect(&dfa).nfa_states().is_empty());
+    }
 }
 /////////////////////////////////////////////////////// MATCHING ///////////////////////////////////////////////////////
 /// We've asserted now that the DFA matches simple patterns correctly
@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod test_enumeration {
+    use crate::automata::{Automaton, Symbol};
+    use crate::test::dfa;
+
+    #[test]
+    fn test_shortlex_order() {
+        let words: Vec<String> = dfa("(a|b)*").words(Some(2)).collect();
+        assert_eq!(words, ["", "a", "b", "aa", "ab", "ba", "bb"]);
+    }
+
+    #[test]
+    fn test_finite_language_ends_without_max_len() {
+        let words: Vec<String> = dfa("c|ab|b|aaa").words(None).collect();
+        assert_eq!(words, ["b", "c", "ab", "aaa"]);
+    }
+
+    #[test]
+    fn test_empty_language() {
+        assert_eq!(dfa("a&b").words(None).count(), 0);
+    }
+
+    #[test]
+    fn test_infinite_language_is_lazy() {
+        let words: Vec<String> = dfa("ba*").words(None).take(4).collect();
+        assert_eq!(words, ["b", "ba", "baa", "baaa"]);
+    }
+
+    #[test]
+    fn test_skips_lengths_without_words() {
+        let words: Vec<String> = dfa("(aaa)*").words(Some(9)).collect();
+        assert_eq!(words, ["", "aaa", "aaaaaa", "aaaaaaaaa"]);
+    }
+
+    #[test]
+    fn test_words_agree_with_counting_and_accept() {
+        let dfa = dfa("a?b+(a|c)?|c+");
+        let words: Vec<String> = dfa.words(Some(6)).collect();
+        assert_eq!(words.len() as u128, dfa.count_up_to(6));
+        assert!(words.iter().all(|w| dfa.accept(w)));
+        assert!(
+            words
+                .windows(2)
+                .all(|w| (w[0].len(), &w[0]) < (w[1].len(), &w[1]))
+        );
+    }
+
+    #[test]
+    fn test_dead_states_are_not_explored() {
+        let dfa = dfa("ab").totalize(
+            &"abcdefghijklmnopqrstuvwxyz"
+                .chars()
+                .map(Symbol::CHAR)
+                .collect(),
+        );
+        let words: Vec<String> = dfa.words(None).collect();
+        assert_eq!(words, ["ab"]);
+    }
+}
@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod test_jflap {
+    use crate::automata::{Automaton, Dfa, Nfa};
+    use crate::parse::parse;
+
+    /// `a(b|λ)c*` as saved by JFLAP 7.1, including its comments and coordinates
+    const SAVED_BY_JFLAP: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?><!--Created with JFLAP 7.1.--><structure>
+	<type>fa</type>
+	<automaton>
+		<!--The list of states.-->
+		<state id="0" name="q0">
+			<x>62.0</x>
+			<y>121.0</y>
+			<initial/>
+		</state>
+		<state id="1" name="q1">
+			<x>180.0</x>
+			<y>121.0</y>
+		</state>
+		<state id="2" name="q2">
+			<x>310.0</x>
+			<y>121.0</y>
+			<final/>
+		</state>
+		<!--The list of transitions.-->
+		<transition>
+			<from>0</from>
+			<to>1</to>
+			<read>a</read>
+		</transition>
+		<transition>
+			<from>1</from>
+			<to>2</to>
+			<read>b</read>
+		</transition>
+		<transition>
+			<from>1</from>
+			<to>2</to>
+			<read/>
+		</transition>
+		<transition>
+			<from>2</from>
+			<to>2</to>
+			<read>c</read>
+		</transition>
+	</automaton>
+</structure>"#;
+
+    fn jff(states: &str, transitions: &str) -> String {
+        format!(
+            "<structure><type>fa</type><automaton>{}{}</automaton></structure>",
+            states, transitions
+        )
+    }
+
+    #[test]
+    fn test_import_nfa() {
+        let nfa = Nfa::from_jff(SAVED_BY_JFLAP).unwrap();
+        assert!(nfa.validate().is_ok());
+        assert_eq!(nfa.states.len(), 3);
+        for word in ["a", "ab", "ac", "abccc"] {
+            assert!(nfa.accept(word), "{}", word);
+        }
+        for word in ["", "b", "abb", "ca"] {
+            assert!(!nfa.accept(word), "{}", word);
+        }
+        // not a dfa because of the empty string
+        assert!(Dfa::from_jff(SAVED_BY_JFLAP).is_err());
+    }
+
+    #[test]
+    fn test_multiple_symbols() {
+        let xml = jff(
+            r#"<state id="0"><initial/></state><state id="1"><final/></state>"#,
+            "<transition><from>0</from><to>1</to><read>abc</read></transition>",
+        );
+        let nfa = Nfa::from_jff(&xml).unwrap();
+        // two intermediate states
+        assert_eq!(nfa.states.len(), 4);
+        assert!(nfa.accept("abc"));
+        assert!(!nfa.accept("a"));
+        assert!(Dfa::from_jff(&xml).is_err());
+    }
+
+    #[test]
+    fn test_jflap_6_layout() {
+        // no <automaton> around states and transitions
+        let xml = r#"<structure><type>fa</type>
+            <state id="3"><initial/><final/></state>
+            <transition><from>3</from><to>3</to><read>x</read></transition>
+        </structure>"#;
+        let dfa = Dfa::from_jff(xml).unwrap();
+        assert!(dfa.accept(""));
+        assert!(dfa.accept("xxx"));
+        assert!(!dfa.accept("y"));
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let nfa = parse("a?b+(a|c)?|c+");
+        let loaded = Nfa::from_jff(&nfa.to_jff()).unwrap();
+        assert!(loaded.states.iter().all(|q| !nfa.states.contains(q)));
+        assert_eq!(loaded.states.len(), nfa.states.len());
+        assert_eq!(loaded.transitions.len(), nfa.transitions.len());
+
+        let dfa = Dfa::from(&nfa);
+        let loaded = Dfa::from_jff(&dfa.to_jff()).unwrap();
+        assert_eq!(loaded.states().len(), dfa.states().len());
+        for word in dfa.words(Some(5)) {
+            assert!(loaded.accept(&word), "{}", word);
+        }
+        assert_eq!(loaded.count_up_to(8), dfa.count_up_to(8));
+    }
+
+    #[test]
+    fn test_export_escapes() {
+        // & is an operator of the pattern syntax
+        let nfa = Nfa::from_json(
+            r#"{ "type": "nfa", "states": [0, 1], "start": 0, "accepting": [1],
+                 "transitions": [{ "from": 0, "to": 0, "class": ["<", "&"] }, { "from": 0, "to": 1, "symbol": " " }] }"#,
+        )
+        .unwrap();
+        let xml = nfa.to_jff();
+        assert!(xml.contains("<read>&lt;</read>"));
+        assert!(xml.contains("<read>&amp;</read>"));
+        assert!(xml.contains("<read>&#32;</read>"));
+        assert!(Nfa::from_jff(&xml).unwrap().accept("<&<& "));
+    }
+
+    #[test]
+    fn test_invalid_input_is_an_error() {
+        let invalid = [
+            String::from(""),
+            String::from("<structure"),
+            String::from("<automaton/>"),
+            String::from("<structure><type>pda</type></structure>"),
+            jff(r#"<state id="0"/>"#, ""),
+            jff(
+                r#"<state id="0"><initial/></state><state id="1"><initial/></state>"#,
+                "",
+            ),
+            jff(r#"<state id="0"><initial/></state><state id="0"/>"#, ""),
+            jff(
+                r#"<state id="0"><initial/></state>"#,
+                "<transition><from>0</from><to>1</to><read>a</read></transition>",
+            ),
+            jff(
+                r#"<state id="0"><initial/></state>"#,
+                "<transition><from>0</from><to>0</to></transition>",
+            ),
+        ];
+        for xml in invalid {
+            assert!(Nfa::from_jff(&xml).is_err(), "{}", xml);
+            assert!(Dfa::from_jff(&xml).is_err(), "{}", xml);
+        }
+    }
+}
@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod test_json {
+    use crate::automata::{Automaton, Dfa, Nfa};
+    use crate::parse::parse;
+
+    const EXAMPLE: &str = r#"{
+        "type": "nfa",
+        "states": [0, 1, 2],
+        "start": 0,
+        "accepting": [2],
+        "transitions": [
+            { "from": 0, "to": 1, "symbol": "a" },
+            { "from": 1, "to": 2, "epsilon": true },
+            { "from": 2, "to": 2, "class": ["0-9", "x"] }
+        ]
+    }"#;
+
+    /// A minimal dfa accepting `a+`, with the states named by the caller
+    fn dfa_json(transitions: &str) -> String {
+        format!(
+            r#"{{ "type": "dfa", "states": [7, 9], "start": 7, "accepting": [9], "transitions": [{}] }}"#,
+            transitions
+        )
+    }
+
+    #[test]
+    fn test_import_nfa() {
+        let nfa = Nfa::from_json(EXAMPLE).unwrap();
+        assert!(nfa.validate().is_ok());
+        assert_eq!(nfa.states.len(), 3);
+        // one transition per char of the class
+        assert_eq!(nfa.transitions.len(), 2 + 11);
+        assert!(nfa.accept("a"));
+        assert!(nfa.accept("a0x9"));
+        assert!(!nfa.accept("ax-"));
+        assert!(!nfa.accept(""));
+    }
+
+    #[test]
+    fn test_export_merges_classes() {
+        let nfa = Nfa::from_json(EXAMPLE).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&nfa.to_json()).unwrap();
+        assert_eq!(json["type"], "nfa");
+        let transitions = json["transitions"].as_array().unwrap();
+        assert_eq!(transitions.len(), 3);
+        assert!(transitions.iter().any(|t| t["symbol"] == "a"));
+        assert!(transitions.iter().any(|t| t["epsilon"] == true));
+        assert!(
+            transitions
+                .iter()
+                .any(|t| t["class"] == serde_json::json!(["0-9", "x"]))
+        );
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let nfa = parse("a?b+(a|c)?|c+");
+        let loaded = Nfa::from_json(&nfa.to_json()).unwrap();
+        // fresh states, so the imported automaton can't clash with the original
+        assert!(loaded.states.iter().all(|q| !nfa.states.contains(q)));
+        assert_eq!(loaded.transitions.len(), nfa.transitions.len());
+
+        let dfa = Dfa::from(&nfa);
+        let loaded = Dfa::from_json(&dfa.to_json()).unwrap();
+        assert_eq!(loaded.states().len(), dfa.states().len());
+        assert_eq!(loaded.alphabet(), dfa.alphabet());
+        for word in dfa.words(Some(5)) {
+            assert!(loaded.accept(&word), "{}", word);
+        }
+        assert_eq!(loaded.count_up_to(8), dfa.count_up_to(8));
+    }
+
+    #[test]
+    fn test_import_dfa() {
+        let json = dfa_json(
+            r#"{ "from": 7, "to": 9, "symbol": "a" }, { "from": 9, "to": 9, "symbol": "a" }"#,
+        );
+        let dfa = Dfa::from_json(&json).unwrap();
+        assert!(dfa.accept("aaa"));
+        assert!(!dfa.accept(""));
+        // a dfa is an nfa as well
+        assert!(Nfa::from_json(&json).unwrap().accept("a"));
+    }
+
+    #[test]
+    fn test_dfa_rejects_nondeterminism() {
+        assert!(Dfa::from_json(EXAMPLE).is_err());
+        let epsilon = dfa_json(r#"{ "from": 7, "to": 9, "epsilon": true }"#);
+        assert!(Dfa::from_json(&epsilon).is_err());
+        let ambiguous = dfa_json(
+            r#"{ "from": 7, "to": 9, "symbol": "a" }, { "from": 7, "to": 7, "class": ["a", "b"] }"#,
+        );
+        assert!(Dfa::from_json(&ambiguous).is_err());
+        // the same transition twice is still deterministic
+        let duplicate = dfa_json(
+            r#"{ "from": 7, "to": 9, "symbol": "a" }, { "from": 7, "to": 9, "class": ["a-c"] }"#,
+        );
+        assert!(Dfa::from_json(&duplicate).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_input_is_an_error() {
+        let invalid = [
+            "",
+            "[]",
+            r#"{ "type": "pda", "states": [0], "start": 0, "accepting": [], "transitions": [] }"#,
+            // unknown states
+            r#"{ "type": "nfa", "states": [0], "start": 1, "accepting": [], "transitions": [] }"#,
+            r#"{ "type": "nfa", "states": [0], "start": 0, "accepting": [1], "transitions": [] }"#,
+            r#"{ "type": "nfa", "states": [0], "start": 0, "accepting": [], "transitions": [{ "from": 0, "to": 1, "symbol": "a" }] }"#,
+            r#"{ "type": "nfa", "states": [0, 0], "start": 0, "accepting": [], "transitions": [] }"#,
+            // labels
+            r#"{ "type": "nfa", "states": [0], "start": 0, "accepting": [], "transitions": [{ "from": 0, "to": 0 }] }"#,
+            r#"{ "type": "nfa", "states": [0], "start": 0, "accepting": [], "transitions": [{ "from": 0, "to": 0, "symbol": "ab" }] }"#,
+            r#"{ "type": "nfa", "states": [0], "start": 0, "accepting": [], "transitions": [{ "from": 0, "to": 0, "epsilon": false }] }"#,
+            r#"{ "type": "nfa", "states": [0], "start": 0, "accepting": [], "transitions": [{ "from": 0, "to": 0, "symbol": "a", "epsilon": true }] }"#,
+            r#"{ "type": "nfa", "states": [0], "start": 0, "accepting": [], "transitions": [{ "from": 0, "to": 0, "class": [] }] }"#,
+            r#"{ "type": "nfa", "states": [0], "start": 0, "accepting": [], "transitions": [{ "from": 0, "to": 0, "class": ["z-a"] }] }"#,
+            r#"{ "type": "nfa", "states": [0], "start": 0, "accepting": [], "transitions": [{ "from": 0, "to": 0, "class": ["abc"] }] }"#,
+        ];
+        for json in invalid {
+            assert!(Nfa::from_json(json).is_err(), "{}", json);
+            assert!(Dfa::from_json(json).is_err(), "{}", json);
+        }
+    }
+}
@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod test_lint {
+    use crate::lint::{LintKind, lint};
+    use std::ops::Range;
+
+    /// The kind and span of every lint of `pattern`
+    fn lints(pattern: &str) -> Vec<(LintKind, Range<usize>)> {
+        lint(pattern)
+            .unwrap()
+            .into_iter()
+            .map(|lint| (lint.kind, lint.span))
+            .collect()
+    }
+
+    #[test]
+    fn test_clean_patterns() {
+        for pattern in [
+            "ab",
+            "a+",
+            "(a|ab)(c|bcd)",
+            "a|b|c",
+            "~a&(a|b)+",
+            "x(a|b)?y",
+        ] {
+            assert_eq!(lints(pattern), [], "{}", pattern);
+        }
+    }
+
+    #[test]
+    fn test_redundant_alternatives() {
+        assert_eq!(lints("a+|a"), [(LintKind::Redundant, 3..4)]);
+        assert_eq!(lints("ab|a+b"), [(LintKind::Redundant, 0..2)]);
+        // of two equal alternatives, the later one is redundant
+        assert_eq!(lints("ab|c|ab"), [(LintKind::Redundant, 5..7)]);
+        // covered by multiple alternatives together
+        assert_eq!(lints("a|b|(a|b)"), [(LintKind::Redundant, 4..9)]);
+        assert_eq!(
+            lint("a|b|(a|b)").unwrap()[0].message,
+            "`(a|b)` is already matched by the other alternatives"
+        );
+        // nested inside of a group
+        assert_eq!(lints("x(ab|ab)y"), [(LintKind::Redundant, 5..7)]);
+        assert_eq!(
+            lints("a|a*"),
+            [
+                (LintKind::Redundant, 0..1),
+                (LintKind::MatchesEmptyWord, 2..4)
+            ]
+        );
+        assert_eq!(
+            lint("a|a*").unwrap()[0].message,
+            "`a` is already matched by `a*`"
+        );
+    }
+
+    #[test]
+    fn test_quantified_empty() {
+        assert_eq!(lints("b(a?)*"), [(LintKind::QuantifiedEmpty, 1..6)]);
+        assert_eq!(lints("b(a*)+"), [(LintKind::QuantifiedEmpty, 1..6)]);
+        assert_eq!(lints("b(a|c?)?"), [(LintKind::QuantifiedEmpty, 1..8)]);
+        // both the outer and the inner quantifier
+        assert_eq!(
+            lints("b((a?)*)+"),
+            [
+                (LintKind::QuantifiedEmpty, 1..9),
+                (LintKind::QuantifiedEmpty, 2..7)
+            ]
+        );
+        // complements are checked as well
+        assert_eq!(lints("b~((a|c)?)+"), [(LintKind::QuantifiedEmpty, 2..11)]);
+    }
+
+    #[test]
+    fn test_never_matches() {
+        assert_eq!(lints("a&b"), [(LintKind::NeverMatches, 0..3)]);
+        // only the innermost part is reported
+        assert_eq!(lints("x(a&b)|c"), [(LintKind::NeverMatches, 2..5)]);
+        assert_eq!(lints("c|x(a&b)y"), [(LintKind::NeverMatches, 4..7)]);
+        // a complement is taken with respect to the entire pattern
+        assert_eq!(lints("x(~(a*)&a*)"), [(LintKind::NeverMatches, 2..10)]);
+        // a branch that never matches isn't redundant on top of that
+        assert_eq!(lints("a|a&b"), [(LintKind::NeverMatches, 2..5)]);
+    }
+
+    #[test]
+    fn test_matches_empty_word() {
+        assert_eq!(lints("a(b|c)*|x?"), [(LintKind::MatchesEmptyWord, 8..10)]);
+        assert_eq!(
+            lint("a|b*").unwrap()[0].message,
+            "The pattern matches the empty word, because `b*` does"
+        );
+        // ... even if only a complement does
+        assert_eq!(lints("~(a|b)|a"), [(LintKind::MatchesEmptyWord, 0..6)]);
+        assert_eq!(lints("a?b*"), [(LintKind::MatchesEmptyWord, 0..4)]);
+        assert_eq!(
+            lint("a?b*").unwrap()[0].message,
+            "The pattern matches the empty word, as every part of it is optional"
+        );
+        // inside of the parentheses around all of it
+        assert_eq!(lints("(a?b*)"), [(LintKind::MatchesEmptyWord, 1..5)]);
+    }
+
+    #[test]
+    fn test_matches_empty_word_on_purpose() {
+        for pattern in ["a*", "(ab)*", "(a|b)?", "~a", "(a*)", "a*&(a|b)*"] {
+            assert_eq!(lints(pattern), [], "{}", pattern);
+        }
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        assert_eq!(lint("a|").unwrap_err().span, 2..2);
+    }
+}
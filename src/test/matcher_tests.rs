@@ -0,0 +1,213 @@
+#[cfg(test)]
+mod test_matcher {
+    use crate::automata::{Deterministic, Dfa, find_all, find_leftmost_longest};
+    use crate::matcher::Matcher;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_is_match() {
+        let matcher = Matcher::new("a?b+(a|c)?").unwrap();
+        assert!(matcher.is_match("abc"));
+        assert!(matcher.is_match("bb"));
+        assert!(!matcher.is_match("abcabc"));
+    }
+
+    #[test]
+    fn test_find() {
+        let matcher = Matcher::new("aab|ac").unwrap();
+        assert_eq!(matcher.find("aac"), Some((1, 2)));
+        assert_eq!(matcher.find("cadaabf"), Some((3, 5)));
+        assert_eq!(matcher.find("cab"), None);
+        assert_eq!(matcher.find_all(""), vec![]);
+        // char indices, not byte offsets
+        assert_eq!(matcher.find("äaac"), Some((2, 3)));
+    }
+
+    #[test]
+    fn test_find_all() {
+        let matcher = Matcher::new("aab|ac").unwrap();
+        assert_eq!(
+            matcher.find_all("aab ac xaac"),
+            vec![(0, 2), (4, 5), (9, 10)]
+        );
+        // leftmost longest without overlaps
+        let matcher = Matcher::new("l+|lo w").unwrap();
+        assert_eq!(matcher.find_all("hello world"), vec![(2, 3), (9, 9)]);
+        let matcher = Matcher::new("aba").unwrap();
+        assert_eq!(matcher.find_all("ababababa"), vec![(0, 2), (4, 6)]);
+    }
+
+    /// The matches of `dfa` in `input`, found one after the other by trying every start
+    fn one_by_one(dfa: &Dfa, input: &str) -> Vec<(usize, usize)> {
+        let chars: Vec<char> = input.chars().collect();
+        let mut matches = Vec::new();
+        let mut from = 0;
+        while let Some((start, end)) =
+            find_leftmost_longest(dfa, &chars[from..].iter().collect::<String>())
+        {
+            matches.push((from + start, from + end));
+            from += end + 1;
+        }
+        matches
+    }
+
+    #[test]
+    fn test_find_all_like_trying_every_start() {
+        let patterns = [
+            "a",
+            "a+",
+            "ab|b",
+            "(a|b)*c|a",
+            "a*b*",
+            "(ab)+|b+a",
+            "a?b+a?",
+            "a*|bc",
+        ];
+        let inputs = [
+            "",
+            "a",
+            "b",
+            "abcabc",
+            "aaaaaaa",
+            "ababcbabcc",
+            "cabbacbbcaab",
+            "xaxbxcx",
+            "äbäb",
+        ];
+        for pattern in patterns {
+            let matcher = Matcher::new(pattern).unwrap();
+            for input in inputs {
+                assert_eq!(
+                    matcher.find_all(input),
+                    one_by_one(&matcher.dfa, input),
+                    "{} in {:?}",
+                    pattern,
+                    input
+                );
+            }
+        }
+    }
+
+    /// Counts the steps taken, to tell linear from quadratic without measuring any time
+    struct Counting<'a> {
+        dfa: &'a Dfa,
+        steps: &'a Cell<usize>,
+    }
+
+    impl Deterministic for Counting<'_> {
+        fn start(&self) -> usize {
+            self.dfa.start()
+        }
+
+        fn step(&self, state: usize, c: char) -> Option<usize> {
+            self.steps.set(self.steps.get() + 1);
+            self.dfa.step(state, c)
+        }
+
+        fn is_accepting(&self, state: usize) -> bool {
+            self.dfa.is_accepting(state)
+        }
+    }
+
+    #[test]
+    fn test_find_all_is_linear() {
+        // every `a` is a match, but each of them could also be the start of a long match up to a `c` that never comes
+        let matcher = Matcher::new("(a|b)*c|a").unwrap();
+        let input = "a".repeat(10_000);
+        let steps = Cell::new(0);
+        let matches = find_all(
+            &Counting {
+                dfa: &matcher.reversed,
+                steps: &steps,
+            },
+            &input,
+            &Counting {
+                dfa: &matcher.dfa,
+                steps: &steps,
+            },
+        )
+        .unwrap();
+        assert_eq!(matches.len(), input.len());
+        assert!(matches.iter().enumerate().all(|(i, m)| *m == (i, i)));
+        assert!(steps.get() < 10 * input.len(), "{} steps", steps.get());
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let error = Matcher::new("(a|b").err().unwrap();
+        assert_eq!(error.message, "Unclosed '('");
+    }
+}
+
+#[cfg(test)]
+mod test_regex_set {
+    use crate::automata::Automaton;
+    use crate::matcher::Matcher;
+    use crate::matcher::set::RegexSet;
+
+    #[test]
+    fn test_matches() {
+        let set = RegexSet::new(["ab*", "a(b|c)", "c*", "~a"]).unwrap();
+        assert_eq!(set.len(), 4);
+        assert_eq!(set.matches("ab"), [0, 1]);
+        assert_eq!(set.matches("aa"), [3]);
+        assert_eq!(set.matches("a"), [0]);
+        assert_eq!(set.matches("ac"), [1]);
+        assert_eq!(set.matches(""), [2, 3]);
+        assert_eq!(set.matches("cc"), [2]);
+        // `~a` complements with respect to its own symbols only, and `b` isn't one of them
+        assert!(set.matches("b").is_empty());
+        assert!(set.matches("x").is_empty());        // the tags are all that's kept of the `Nfa` states
+        assert!(set.dfa.nfa_states().is_empty());
+    }
+
+    #[test]
+    fn test_matches_in() {
+        let set = RegexSet::new(["error", "warn(ing)?", "disk (full|failure)", "x+"]).unwrap();
+        assert_eq!(set.matches_in("warning: disk full"), [1, 2]);
+        assert_eq!(set.matches_in("[error] disk failure, see log"), [0, 2]);
+        assert!(set.matches_in("everything is fine").is_empty());
+        assert_eq!(set.matches_in("xx-warn-error-xyz"), [0, 1, 3]);
+        // matches may overlap
+        assert_eq!(
+            RegexSet::new(["ab", "bc", "abc"])
+                .unwrap()
+                .matches_in("zabcz"),
+            [0, 1, 2]
+        );
+        assert_eq!(RegexSet::new(["a*"]).unwrap().matches_in(""), [0]);
+    }
+
+    #[test]
+    fn test_same_as_separately() {
+        let patterns = ["a(b|c)*", "b+a", "(ab)?c", "c&c*", "~(a|b)"];
+        let set = RegexSet::new(patterns).unwrap();
+        let matchers: Vec<Matcher> = patterns.iter().map(|p| Matcher::new(p).unwrap()).collect();
+        for input in ["", "a", "abcb", "bba", "abc", "c", "cab", "ba", "aa"] {
+            let expected: Vec<usize> = (0..patterns.len())
+                .filter(|i| matchers[*i].is_match(input))
+                .collect();
+            assert_eq!(set.matches(input), expected, "{}", input);
+            assert_eq!(set.dfa.accept(input), !expected.is_empty(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_many_patterns() {
+        let patterns: Vec<String> = (0..500).map(|i| format!("id{}x", i)).collect();
+        let set = RegexSet::new(&patterns).unwrap();
+        assert_eq!(set.matches("id42x"), [42]);
+        assert_eq!(set.matches_in("user id7x, then id499x"), [7, 499]);
+        assert!(set.matches_in("id500x").is_empty());
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let error = RegexSet::new(["a", "b|", "(c"]).err().unwrap();
+        assert_eq!(error.index, 1);
+        assert_eq!(error.to_string(), "pattern 1: Empty alternative at 2..2");
+        let empty = RegexSet::new(Vec::<&str>::new()).unwrap();
+        assert!(empty.is_empty());
+        assert!(empty.matches_in("abc").is_empty());
+    }
+}
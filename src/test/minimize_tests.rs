@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod test_minimize {
+    use crate::automata::{Automaton, Symbol};
+    use crate::test::dfa;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_minimal_size() {
+        assert_eq!(dfa("(a|b)*abb").minimize().states().len(), 4);
+        assert_eq!(dfa("a*|a+|aa").minimize().states().len(), 1);
+        assert_eq!(dfa("ab|cb").minimize().states().len(), 3);
+        assert_eq!(dfa("(ab)+|(ab)*ab").minimize().states().len(), 3);
+        // (a|b)*a(a|b) needs to remember the last two symbols
+        assert_eq!(dfa("(a|b)*a(a|b)").minimize().states().len(), 4);
+    }
+
+    #[test]
+    fn test_same_language() {
+        for pattern in [
+            "(a|b)*abb",
+            "a?b+(a|c)?|c+",
+            "~(a*)&(a|b)*",
+            "(ab|a)*(ba|b)?",
+        ] {
+            let dfa = dfa(pattern);
+            let minimal = dfa.minimize();
+            assert!(minimal.validate().is_ok(), "{}", pattern);
+            assert!(dfa.symmetric_difference(&minimal).is_empty(), "{}", pattern);
+            assert!(minimal.states().len() <= dfa.states().len(), "{}", pattern);
+        }
+    }
+
+    #[test]
+    fn test_idempotent() {
+        let minimal = dfa("(a|b)*a(a|b)").minimize();
+        assert_eq!(minimal.minimize().states().len(), minimal.states().len());
+    }
+
+    #[test]
+    fn test_empty_language() {
+        let minimal = dfa("a&b").minimize();
+        assert_eq!(minimal.states().len(), 1);
+        assert!(minimal.is_empty());
+        assert!(minimal.transitions().is_empty());
+    }
+
+    #[test]
+    fn test_keeps_alphabet() {
+        // the dead state after `b` is dropped, but `b` still belongs to the alphabet
+        let minimal = dfa("a|ab&a").minimize();
+        assert!(minimal.alphabet().contains(&Symbol::CHAR('b')));
+        assert!(minimal.complement(&HashSet::new()).accept("b"));
+    }
+}
@@ -2,3 +2,27 @@ pub mod nfa_tests;
 mod parse_test;
 mod dfa_tests;
 mod finding_test;
+mod boolean_tests;
+mod query_tests;
+mod counting_tests;
+mod enumerate_tests;
+mod sampling_tests;
+mod witness_tests;
+mod minimize_tests;
+mod trace_tests;
+mod lint_tests;
+mod ambiguity_tests;
+mod render_tests;
+mod binary_tests;
+mod json_tests;
+mod jflap_tests;
+mod codegen_tests;
+mod matcher_tests;
+
+use crate::automata::Dfa;
+use crate::parse::parse;
+
+/// The `Dfa` of `pattern`, which most tests start from
+pub(crate) fn dfa(pattern: &str) -> Dfa {
+    Dfa::from(&parse(pattern))
+}
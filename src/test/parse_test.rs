@@ -135,3 +135,236 @@ mod test_parse {
     //     println!("accept took: {:?}", duration);
     // }
 }
+
+#[cfg(test)]
+mod test_parse_boolean_operators {
+    use crate::automata::Automaton;
+    use crate::parse::parse;
+    use crate::test::dfa;
+
+    #[test]
+    fn test_intersection() {
+        // at least two a's and an even length
+        let pattern = "(a|b)*a(a|b)*a(a|b)*&((a|b)(a|b))*";
+        let dfa = dfa(pattern);
+        println!("{:?}", dfa);
+        assert!(dfa.accept("aa"));
+        assert!(dfa.accept("abab"));
+        assert!(dfa.accept("bbaa"));
+        // not matching
+        assert!(!dfa.accept(""));
+        assert!(!dfa.accept("ab"));
+        assert!(!dfa.accept("aaa"));
+        assert!(!dfa.accept("abbb"));
+    }
+
+    #[test]
+    fn test_complement() {
+        let pattern = "~(ab)";
+        let dfa = dfa(pattern);
+        println!("{:?}", dfa);
+        assert!(dfa.accept(""));
+        assert!(dfa.accept("a"));
+        assert!(dfa.accept("ba"));
+        assert!(dfa.accept("abab"));
+        // not matching
+        assert!(!dfa.accept("ab"));
+        // symbols outside of the pattern's alphabet are never accepted
+        assert!(!dfa.accept("c"));
+    }
+
+    #[test]
+    fn test_complement_binds_looser_than_quantifiers() {
+        let dfa = dfa("~a*b");
+        // (~(a*))b
+        assert!(dfa.accept("bb"));
+        assert!(dfa.accept("abb"));
+        assert!(dfa.accept("bab"));
+        assert!(!dfa.accept("b"));
+        assert!(!dfa.accept("aab"));
+    }
+
+    #[test]
+    fn test_complement_uses_alphabet_of_entire_pattern() {
+        let dfa = dfa("~a&(a|b)*");
+        assert!(dfa.accept(""));
+        assert!(dfa.accept("b"));
+        assert!(dfa.accept("aa"));
+        assert!(!dfa.accept("a"));
+    }
+
+    #[test]
+    fn test_double_complement() {
+        let dfa = dfa("~~(a|b)");
+        assert!(dfa.accept("a"));
+        assert!(dfa.accept("b"));
+        assert!(!dfa.accept(""));
+        assert!(!dfa.accept("ab"));
+    }
+
+    #[test]
+    fn test_precedence() {
+        // (a&a*)|b
+        let dfa = dfa("a&a*|b");
+        assert!(dfa.accept("a"));
+        assert!(dfa.accept("b"));
+        assert!(!dfa.accept("aa"));
+        assert!(!dfa.accept(""));
+    }
+
+    #[test]
+    fn test_identifier_that_is_not_a_keyword() {
+        let pattern = "(f|i|n|o|r)(f|i|n|o|r|0|1)*&~(if|for|in)";
+        let dfa = dfa(pattern);
+        assert!(dfa.accept("i"));
+        assert!(dfa.accept("iff"));
+        assert!(dfa.accept("fo"));
+        assert!(dfa.accept("info"));
+        assert!(dfa.accept("for1"));
+        // not matching
+        assert!(!dfa.accept("if"));
+        assert!(!dfa.accept("for"));
+        assert!(!dfa.accept("in"));
+        assert!(!dfa.accept("1f"));
+        assert!(!dfa.accept(""));
+    }
+
+    #[test]
+    fn test_nfa_of_intersection_accepts() {
+        let nfa = parse("a*&aa*");
+        assert!(nfa.validate().is_ok());
+        assert!(nfa.accept("a"));
+        assert!(nfa.accept("aaa"));
+        assert!(!nfa.accept(""));
+    }
+
+    #[test]
+    #[should_panic(expected = "Nothing to complement")]
+    fn test_dangling_complement() {
+        parse("a~");
+    }
+}
+
+#[cfg(test)]
+mod test_try_parse {
+    use crate::automata::{Automaton, Dfa};
+    use crate::parse::{ParseError, try_parse};
+
+    fn error(pattern: &str) -> ParseError {
+        try_parse(pattern).unwrap_err()
+    }
+
+    #[test]
+    fn test_valid() {
+        let nfa = try_parse("(a|b)*c&~c").unwrap();
+        assert!(nfa.validate().is_ok());
+        assert!(Dfa::from(&nfa).accept("abc"));
+    }
+
+    #[test]
+    fn test_errors_with_span() {
+        assert_eq!(error("").message, "Empty pattern");
+        assert_eq!(error("a|").span, 2..2);
+        assert_eq!(error("a||b").span, 2..3);
+        assert_eq!(error("a)").message, "Unexpected ')'");
+        assert_eq!(error("a)").span, 1..2);
+        let unclosed = error("(a|(b)");
+        assert_eq!(unclosed.message, "Unclosed '('");
+        assert_eq!(unclosed.span, 0..1);
+        assert_eq!(error("a()").span, 1..3);
+        assert_eq!(error("*a").message, "Nothing to quantify");
+        assert_eq!(error("(|a)").message, "Empty alternative");
+        assert_eq!(error("a**").message, "Illegal stacking of quantifiers");
+        assert_eq!(error("a**").span, 2..3);
+        assert_eq!(error("~*a").message, "Nothing to complement");
+        assert_eq!(error("a&~").span, 2..3);
+        assert_eq!(error("a&~").to_string(), "Nothing to complement at 2..3");
+    }
+
+    #[test]
+    fn test_multibyte_symbols() {
+        let dfa = Dfa::from(&try_parse("(ä|€)+").unwrap());
+        assert!(dfa.accept("ä€ä"));
+        // spans are byte offsets
+        assert_eq!(error("€|").span, 4..4);
+    }
+
+    #[test]
+    fn test_never_panics() {
+        // every pattern over these symbols of up to 4 chars, either parsed or rejected
+        let symbols = ['a', '(', ')', '|', '&', '~', '*', '?'];
+        let mut patterns = vec![String::new()];
+        for _ in 0..4 {
+            patterns = patterns
+                .iter()
+                .flat_map(|p| symbols.iter().map(move |c| format!("{}{}", p, c)))
+                .collect();
+            for pattern in &patterns {
+                if let Ok(nfa) = try_parse(pattern) {
+                    assert!(nfa.validate().is_ok(), "{}", pattern);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_spans {
+    use crate::automata::{Nfa, Symbol};
+    use crate::parse::parse;
+    use std::ops::Range;
+
+    /// The span of the state the transition with `c` comes from
+    fn span_of(nfa: &Nfa, c: char) -> Range<usize> {
+        let (from, _, _) = nfa
+            .transitions
+            .iter()
+            .find(|(_, with, _)| *with == Symbol::CHAR(c))
+            .unwrap();
+        nfa.spans[from].clone()
+    }
+
+    #[test]
+    fn test_spans() {
+        let nfa = parse("ab|c");
+        assert_eq!(span_of(&nfa, 'a'), 0..1);
+        assert_eq!(span_of(&nfa, 'b'), 1..2);
+        assert_eq!(span_of(&nfa, 'c'), 3..4);
+        assert_eq!(nfa.spans[&nfa.q_start], 0..4);
+
+        let nfa = parse("x(a*)");
+        assert_eq!(span_of(&nfa, 'a'), 2..3);
+        assert_eq!(nfa.spans[&nfa.q_start], 0..1);
+        // the state introduced by the quantifier
+        let starred: Vec<&Range<usize>> =
+            nfa.spans.values().filter(|span| **span == (2..4)).collect();
+        assert_eq!(starred.len(), 1);
+
+        // states of the product construction don't stem from a single fragment, only the entire term
+        assert_eq!(span_of(&parse("x(a&a*)"), 'a'), 2..6);
+        assert_eq!(span_of(&parse("x|~b"), 'b'), 2..4);
+
+        // spans are byte ranges
+        let nfa = parse("é|a");
+        assert_eq!(span_of(&nfa, 'é'), 0..2);
+        assert_eq!(span_of(&nfa, 'a'), 3..4);
+
+        assert!(Nfa::from_symbol(&Symbol::CHAR('a')).spans.is_empty());
+    }
+
+    #[test]
+    fn test_every_state_has_a_span() {
+        for pattern in ["a", "(ab|c)*d", "~(a?b)+&(a|b)*", "((a))|b+c?"] {
+            let nfa = parse(pattern);
+            for state in &nfa.states {
+                let span = &nfa.spans[state];
+                assert!(
+                    pattern.get(span.clone()).is_some(),
+                    "{} {:?}",
+                    pattern,
+                    span
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod test_structural_queries {
+    use crate::automata::{Dfa, Nfa, Symbol};
+    use crate::parse::parse;
+    use crate::test::dfa;
+    use std::collections::HashSet;
+
+    fn alphabet(symbols: &str) -> HashSet<Symbol> {
+        symbols.chars().map(Symbol::CHAR).collect()
+    }
+
+    #[test]
+    fn test_accepts_empty_word() {
+        for (pattern, expected) in [("a*", true), ("a?b?", true), ("a+", false), ("ab|c", false)] {
+            let nfa = parse(pattern);
+            assert_eq!(nfa.accepts_empty_word(), expected, "{}", pattern);
+            assert_eq!(
+                Dfa::from(&nfa).accepts_empty_word(),
+                expected,
+                "{}",
+                pattern
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_empty() {
+        for (pattern, expected) in [
+            ("a&b", true),
+            ("a*&b+", true),
+            ("a", false),
+            ("a*&a+", false),
+        ] {
+            let nfa = parse(pattern);
+            assert_eq!(nfa.is_empty(), expected, "{}", pattern);
+            assert_eq!(Dfa::from(&nfa).is_empty(), expected, "{}", pattern);
+        }
+    }
+
+    #[test]
+    fn test_empty_language_symbol() {
+        let nfa = Nfa::from_symbol(&Symbol::EMPTY);
+        assert!(nfa.is_empty());
+        assert!(nfa.is_finite());
+        assert!(!nfa.accepts_empty_word());
+    }
+
+    #[test]
+    fn test_is_universal() {
+        let ab = alphabet("ab");
+        for (pattern, expected) in [
+            ("(a|b)*", true),
+            ("(a*b*)*", true),
+            ("(a|b)+", false),
+            ("a*", false),
+        ] {
+            let nfa = parse(pattern);
+            assert_eq!(nfa.is_universal(&ab), expected, "{}", pattern);
+            assert_eq!(Dfa::from(&nfa).is_universal(&ab), expected, "{}", pattern);
+        }
+        // universal over its own alphabet, but not over a larger one
+        assert!(parse("a*").is_universal(&HashSet::new()));
+        assert!(!parse("(a|b)*").is_universal(&alphabet("abc")));
+    }
+
+    #[test]
+    fn test_is_finite() {
+        for (pattern, expected) in [
+            ("abc", true),
+            ("a?b?c?", true),
+            ("a|bc|def", true),
+            ("a*", false),
+            ("ab+c", false),
+            ("(ab|c)*d", false),
+            // the loop can never lead to an accepting state
+            ("a*b&c", true),
+        ] {
+            let nfa = parse(pattern);
+            assert_eq!(nfa.is_finite(), expected, "{}", pattern);
+            assert_eq!(Dfa::from(&nfa).is_finite(), expected, "{}", pattern);
+        }
+    }
+
+    #[test]
+    fn test_dead_state_loop_is_finite() {
+        // totalizing adds a looping dead state, which must not make the language infinite
+        let dfa = dfa("ab").totalize(&alphabet("abc"));
+        assert!(dfa.is_finite());
+        assert!(!dfa.is_empty());
+    }
+
+    #[test]
+    fn test_epsilon_cycle_is_finite() {
+        // (a?)* has epsilon cycles, but only accepts words over a, so it's infinite
+        assert!(!parse("(a?)*").is_finite());
+        // an epsilon-only loop around the empty word
+        let mut nfa = Nfa::from_symbol(&Symbol::EPSILON);
+        nfa.klenee(true);
+        assert!(nfa.is_finite());
+    }
+}
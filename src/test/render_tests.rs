@@ -0,0 +1,200 @@
+#[cfg(test)]
+mod test_symbol_class {
+    use crate::automata::Symbol;
+    use crate::render::symbol_class;
+
+    fn symbols(chars: &str) -> Vec<Symbol> {
+        chars.chars().map(Symbol::CHAR).collect()
+    }
+
+    #[test]
+    fn test_single_symbol() {
+        assert_eq!(symbol_class(&symbols("a")), "a");
+    }
+
+    #[test]
+    fn test_ranges() {
+        assert_eq!(symbol_class(&symbols("fedcba")), "a-f");
+        assert_eq!(symbol_class(&symbols("abcx")), "a-c,x");
+        assert_eq!(symbol_class(&symbols("ab")), "a,b");
+        assert_eq!(symbol_class(&symbols("0123456789az")), "0-9,a,z");
+    }
+
+    #[test]
+    fn test_epsilon() {
+        assert_eq!(symbol_class(&[Symbol::EPSILON]), "ε");
+        assert_eq!(symbol_class(&[Symbol::EPSILON, Symbol::CHAR('a')]), "a,ε");
+    }
+}
+
+#[cfg(test)]
+mod test_dot {
+    use crate::automata::{Dfa, Nfa, Symbol};
+    use crate::parse::parse;
+    use crate::test::dfa;
+    use std::collections::{HashMap, HashSet};
+
+    #[test]
+    fn test_nfa_to_dot() {
+        let nfa = Nfa::new(
+            vec![0, 1, 2],
+            HashSet::from([
+                (0, Symbol::CHAR('a'), 1),
+                (1, Symbol::EPSILON, 2),
+                (2, Symbol::CHAR('b'), 2),
+            ]),
+            0,
+            HashSet::from([2]),
+        );
+        let dot = nfa.to_dot();
+        println!("{}", dot);
+        assert_eq!(
+            dot,
+            "digraph Nfa {\n\
+            \trankdir=LR;\n\
+            \tstart [shape=point, style=invis];\n\
+            \tq0 [shape=circle, label=\"0\"];\n\
+            \tq1 [shape=circle, label=\"1\"];\n\
+            \tq2 [shape=doublecircle, label=\"2\"];\n\
+            \tstart -> q0;\n\
+            \tq0 -> q1 [label=\"a\"];\n\
+            \tq1 -> q2 [label=\"ε\"];\n\
+            \tq2 -> q2 [label=\"b\"];\n\
+            }"
+        );
+    }
+
+    #[test]
+    fn test_dfa_to_dot_merges_parallel_edges() {
+        let mut transitions: HashMap<(usize, Symbol), usize> =
+            "abcdx".chars().map(|c| ((0, Symbol::CHAR(c)), 1)).collect();
+        transitions.insert((1, Symbol::CHAR('y')), 2);
+        let dfa = Dfa::new(vec![0, 1, 2], transitions, 0, HashSet::from([2]));
+        let dot = dfa.to_dot();
+        println!("{}", dot);
+        assert!(dot.starts_with("digraph Dfa {"));
+        assert!(dot.contains("start -> q0;"));
+        assert!(dot.contains("q0 -> q1 [label=\"a-d,x\"];"));
+        assert!(dot.contains("q1 -> q2 [label=\"y\"];"));
+        assert!(dot.contains("q2 [shape=doublecircle"));
+        assert_eq!(dot.matches("doublecircle").count(), 1);
+        assert_eq!(dot.matches(" -> ").count(), 3);
+        assert!(!dot.contains("xlabel"));
+    }
+
+    #[test]
+    fn test_dfa_from_pattern_to_dot() {
+        let dfa = dfa("a*b");
+        let dot = dfa.to_dot();
+        println!("{}", dot);
+        assert!(dot.contains(&format!("start -> q{};", dfa.q_start)));
+        assert_eq!(dot.matches("doublecircle").count(), dfa.q_accepting().len());
+        assert_eq!(dot.matches(" -> ").count(), 1 + dfa.transitions().len());
+        assert!(!dot.contains("xlabel"));
+        // unless asked to keep them, when every state is labelled with the nfa states it stands for
+        let dfa = Dfa::from_with_provenance(&parse("a*b"));
+        assert_eq!(
+            dfa.to_dot().matches("xlabel=\"{").count(),
+            dfa.states().len()
+        );
+    }
+
+    #[test]
+    fn test_dot_escapes_labels() {
+        let dfa = Dfa::from(&Nfa::from_symbol(&Symbol::CHAR('"')));
+        assert!(dfa.to_dot().contains("[label=\"\\\"\"]"));
+    }
+}
+
+#[cfg(test)]
+mod test_mermaid {
+    use crate::automata::{Dfa, Symbol};
+    use std::collections::{HashMap, HashSet};
+
+    #[test]
+    fn test_dfa_to_mermaid() {
+        let dfa = Dfa::new(
+            vec![0, 1],
+            HashMap::from([
+                ((0, Symbol::CHAR('a')), 1),
+                ((0, Symbol::CHAR('b')), 1),
+                ((0, Symbol::CHAR('c')), 1),
+                ((1, Symbol::CHAR(':')), 0),
+            ]),
+            0,
+            HashSet::from([1]),
+        );
+        let mermaid = dfa.to_mermaid();
+        println!("{}", mermaid);
+        assert_eq!(
+            mermaid,
+            "stateDiagram-v2\n\
+            \tdirection LR\n\
+            \tq0 : 0\n\
+            \tq1 : 1\n\
+            \t[*] --> q0\n\
+            \tq0 --> q1 : a-c\n\
+            \tq1 --> q0 : #58;\n\
+            \tq1 --> [*]"
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_table {
+    use crate::automata::{Dfa, Symbol};
+    use crate::parse::parse;
+    use crate::render::state_set;
+    use crate::test::dfa;
+    use std::collections::{HashMap, HashSet};
+
+    #[test]
+    fn test_dfa_to_table() {
+        let dfa = Dfa::new(
+            vec![1, 0, 10],
+            HashMap::from([
+                ((0, Symbol::CHAR('a')), 1),
+                ((0, Symbol::CHAR('b')), 1),
+                ((0, Symbol::CHAR('c')), 1),
+                ((1, Symbol::CHAR('a')), 1),
+                ((1, Symbol::CHAR('b')), 1),
+                ((1, Symbol::CHAR('c')), 1),
+                ((1, Symbol::CHAR('x')), 10),
+            ]),
+            0,
+            HashSet::from([1, 10]),
+        );
+        let table = dfa.to_table();
+        println!("{}", table);
+        let expected = [
+            "       | a-c | x",
+            "->  0  | 1   | -",
+            "  * 1  | 1   | 10",
+            "  * 10 | -   | -",
+        ];
+        assert_eq!(table, expected.join("\n"));
+        assert_eq!(format!("{}", dfa), table);
+    }
+
+    #[test]
+    fn test_table_lists_nfa_states() {
+        assert!(!dfa("ab").to_table().contains("Nfa states"));
+        let dfa = Dfa::from_with_provenance(&parse("ab"));
+        let table = dfa.to_table();
+        println!("{}", table);
+        assert!(table.lines().next().unwrap().ends_with("| Nfa states"));
+        for (state, nfa_states) in dfa.nfa_states() {
+            let row = table
+                .lines()
+                .find(|row| {
+                    row.split(" | ")
+                        .next()
+                        .unwrap()
+                        .ends_with(&format!(" {}", state))
+                })
+                .unwrap();
+            assert!(row.ends_with(&state_set(nfa_states)));
+        }
+        assert!(!dfa.without_nfa_states().to_table().contains("Nfa states"));
+    }
+}
@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod test_sampling {
+    use crate::automata::Automaton;
+    use crate::test::dfa;
+    use rand_core::RngCore;
+    use std::collections::HashMap;
+
+    /// Tiny seeded splitmix64, good enough to get reproducible tests without pulling in `rand`
+    struct SplitMix(u64);
+
+    impl RngCore for SplitMix {
+        fn next_u32(&mut self) -> u32 {
+            self.next_u64() as u32
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+            let mut z = self.0;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+            z ^ (z >> 31)
+        }
+
+        fn fill_bytes(&mut self, dst: &mut [u8]) {
+            for byte in dst {
+                *byte = self.next_u64() as u8;
+            }
+        }
+    }
+
+    #[test]
+    fn test_sample_is_accepted() {
+        let dfa = dfa("a?b+(a|c)?|c+");
+        let mut rng = SplitMix(42);
+        for len in 1..10 {
+            let word = dfa.sample(len, &mut rng).unwrap();
+            assert_eq!(word.chars().count(), len);
+            assert!(dfa.accept(&word), "{}", word);
+        }
+    }
+
+    #[test]
+    fn test_sample_without_words_of_length() {
+        let mut rng = SplitMix(42);
+        assert!(dfa("(aa)*").sample(3, &mut rng).is_none());
+        assert!(dfa("a&b").sample(1, &mut rng).is_none());
+        assert!(dfa("a&b").sample_up_to(10, &mut rng).is_none());
+        assert_eq!(dfa("a*").sample(0, &mut rng).unwrap(), "");
+    }
+
+    #[test]
+    fn test_sample_is_reproducible() {
+        let dfa = dfa("(a|b|c)*");
+        let first: Vec<_> = (0..5)
+            .map(|_| dfa.sample(8, &mut SplitMix(7)).unwrap())
+            .collect();
+        assert!(first.iter().all(|w| *w == first[0]));
+    }
+
+    #[test]
+    fn test_sample_is_roughly_uniform() {
+        // `ab` matches both alternatives, but must not be drawn any more often than the other words
+        let dfa = dfa("(a|b)(a|b)|abc*");
+        let mut rng = SplitMix(1);
+        let mut histogram: HashMap<String, usize> = HashMap::new();
+        for _ in 0..4000 {
+            *histogram
+                .entry(dfa.sample(2, &mut rng).unwrap())
+                .or_default() += 1;
+        }
+        assert_eq!(histogram.len(), 4);
+        assert!(
+            histogram.values().all(|n| (800..1200).contains(n)),
+            "{:?}",
+            histogram
+        );
+    }
+
+    #[test]
+    fn test_sample_up_to_weighs_lengths() {
+        // 1 word of length 0, 2 of length 1, 4 of length 2
+        let dfa = dfa("(a|b)?(a|b)?");
+        let mut rng = SplitMix(3);
+        let mut lengths = [0usize; 3];
+        for _ in 0..7000 {
+            let word = dfa.sample_up_to(2, &mut rng).unwrap();
+            assert!(dfa.accept(&word));
+            lengths[word.len()] += 1;
+        }
+        assert!((800..1200).contains(&lengths[0]), "{:?}", lengths);
+        assert!((1800..2200).contains(&lengths[1]), "{:?}", lengths);
+        assert!((3700..4300).contains(&lengths[2]), "{:?}", lengths);
+    }
+}
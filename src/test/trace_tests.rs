@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod test_trace {
+    use crate::automata::trace::Outcome;
+    use crate::automata::{Automaton, Dfa};
+    use crate::parse::parse;
+    use crate::test::dfa;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_dfa_outcomes() {
+        let dfa = dfa("ab+");
+        let trace = dfa.trace("abb");
+        assert_eq!(trace.outcome, Outcome::Accepted);
+        assert!(trace.is_accepted());
+        assert_eq!(trace.steps.len(), 4);
+        assert_eq!(trace.steps[0].state, dfa.q_start);
+        assert!(dfa.q_accepting().contains(&trace.last().state));
+
+        let trace = dfa.trace("a");
+        assert_eq!(trace.outcome, Outcome::Rejected);
+        assert_eq!(trace.steps.len(), 2);
+        assert_eq!(dfa.trace("").outcome, Outcome::Rejected);
+
+        let trace = dfa.trace("abab");
+        assert_eq!(
+            trace.outcome,
+            Outcome::Died {
+                position: 2,
+                symbol: 'a'
+            }
+        );
+        // the steps for "ab", but nothing after that
+        assert_eq!(trace.steps.len(), 3);
+    }
+
+    #[test]
+    fn test_nfa_outcomes() {
+        let nfa = parse("a*b|ac");
+        assert_eq!(nfa.trace("aab").outcome, Outcome::Accepted);
+        assert_eq!(nfa.trace("aa").outcome, Outcome::Rejected);
+        assert_eq!(
+            nfa.trace("acb").outcome,
+            Outcome::Died {
+                position: 2,
+                symbol: 'b'
+            }
+        );
+        // positions are char indices
+        assert_eq!(
+            parse("ä+").trace("ääb").outcome,
+            Outcome::Died {
+                position: 2,
+                symbol: 'b'
+            }
+        );
+        let start: BTreeSet<usize> = nfa.ec(nfa.q_start).into_iter().collect();
+        assert_eq!(nfa.trace("").steps, [start]);
+    }
+
+    #[test]
+    fn test_dfa_agrees_with_nfa() {
+        let nfa = parse("(ab|a)*(ba|b)?");
+        let dfa = Dfa::from_with_provenance(&nfa);
+        for word in ["", "ab", "aba", "abba", "bb", "abc", "aabab"] {
+            let dfa_trace = dfa.trace(word);
+            let nfa_trace = nfa.trace(word);
+            assert_eq!(dfa_trace.outcome, nfa_trace.outcome, "{}", word);
+            assert_eq!(dfa_trace.is_accepted(), dfa.accept(word), "{}", word);
+            // every dfa state stands for exactly the set of states the nfa is in
+            let sets: Vec<BTreeSet<usize>> = dfa_trace
+                .steps
+                .into_iter()
+                .map(|step| step.nfa_states.unwrap())
+                .collect();
+            assert_eq!(sets, nfa_trace.steps, "{}", word);
+        }
+    }
+
+    #[test]
+    fn test_nfa_states_need_provenance() {
+        let nfa = parse("ab|ac");
+        let trace = Dfa::from_with_provenance(&nfa).trace("ab");
+        assert!(trace.steps.iter().all(|step| step.nfa_states.is_some()));
+        let start: BTreeSet<usize> = nfa.ec(nfa.q_start).into_iter().collect();
+        assert_eq!(trace.steps[0].nfa_states, Some(start));
+
+        let trace = Dfa::from(&nfa).trace("ab");
+        assert!(trace.steps.iter().all(|step| step.nfa_states.is_none()));
+    }
+
+    #[test]
+    fn test_no_nfa_states_without_powerset_construction() {
+        let product = dfa("a|b").intersect(&dfa("a*"));
+        let trace = product.trace("a");
+        assert!(trace.is_accepted());
+        assert!(trace.steps.iter().all(|step| step.nfa_states.is_none()));
+    }
+}
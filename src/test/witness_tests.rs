@@ -0,0 +1,38 @@
+#[cfg(test)]
+mod test_witnesses {
+    use crate::automata::Symbol;
+    use crate::test::dfa;
+    use std::collections::HashSet;
+
+    fn alphabet(symbols: &str) -> HashSet<Symbol> {
+        symbols.chars().map(Symbol::CHAR).collect()
+    }
+
+    #[test]
+    fn test_shortest_accepted() {
+        assert_eq!(dfa("a*").shortest_accepted().unwrap(), "");
+        assert_eq!(dfa("abc|de").shortest_accepted().unwrap(), "de");
+        assert_eq!(dfa("a+b").shortest_accepted().unwrap(), "ab");
+        // lexicographically smallest of the shortest words
+        assert_eq!(dfa("cb|ca|ba|bb").shortest_accepted().unwrap(), "ba");
+        assert!(dfa("a&b").shortest_accepted().is_none());
+    }
+
+    #[test]
+    fn test_shortest_rejected() {
+        assert_eq!(dfa("a+").shortest_rejected(&HashSet::new()).unwrap(), "");
+        assert_eq!(dfa("a?").shortest_rejected(&HashSet::new()).unwrap(), "aa");
+        assert_eq!(dfa("a*").shortest_rejected(&alphabet("b")).unwrap(), "b");
+        assert_eq!(
+            dfa("(a|b)*").shortest_rejected(&alphabet("c")).unwrap(),
+            "c"
+        );
+        assert_eq!(
+            dfa("(a|b)*&~(b*)")
+                .shortest_rejected(&HashSet::new())
+                .unwrap(),
+            ""
+        );
+        assert!(dfa("(a|b)*").shortest_rejected(&alphabet("ab")).is_none());
+    }
+}
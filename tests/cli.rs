@@ -0,0 +1,143 @@
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+fn rdfa(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rdfa"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // rdfa exits without reading stdin if the pattern is invalid. If it's gone before the input is written, the pipe is
+    // closed already, so whether writing fails depends on which of the two processes is faster.
+    if let Err(e) = child.stdin.take().unwrap().write_all(stdin.as_bytes()) {
+        assert_eq!(e.kind(), std::io::ErrorKind::BrokenPipe);
+    }
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+/// A fresh directory with `files` in it, relative paths may contain subdirectories
+fn directory(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rusty_dfa_cli_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    for (path, content) in files {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+    dir
+}
+
+const TEXT: &str = "hello world\nfoo bar\nHello aab ac\n";
+
+#[test]
+fn test_selects_lines() {
+    let output = rdfa(&["aab|ac|wor"], TEXT);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "hello world\nHello aab ac\n");
+
+    let output = rdfa(&["xyz"], TEXT);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "");
+}
+
+#[test]
+fn test_flags() {
+    assert_eq!(stdout(&rdfa(&["-v", "o"], "a\nb\nfoo\n")), "a\nb\n");
+    assert_eq!(stdout(&rdfa(&["-c", "o"], TEXT)), "3\n");
+    assert_eq!(stdout(&rdfa(&["-c", "-v", "ab"], TEXT)), "2\n");
+    assert_eq!(stdout(&rdfa(&["-n", "bar"], TEXT)), "2:foo bar\n");
+    assert_eq!(stdout(&rdfa(&["-o", "a+b|ac"], TEXT)), "aab\nac\n");
+    assert_eq!(
+        stdout(&rdfa(&["-o", "-n", "l+"], "hello world\n")),
+        "1:ll\n1:l\n"
+    );
+    assert_eq!(
+        stdout(&rdfa(&["-i", "hello"], TEXT)),
+        "hello world\nHello aab ac\n"
+    );
+    assert_eq!(stdout(&rdfa(&["-i", "-o", "ÄB"], "xäbÄbx\n")), "äb\nÄb\n");
+}
+
+#[test]
+fn test_empty_matches() {
+    // like grep, a pattern matching the empty word selects every line, even those without a non-empty match
+    let output = rdfa(&["a*"], "baa\nb\n\n");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "baa\nb\n\n");
+    assert_eq!(stdout(&rdfa(&["-c", "a*"], "baa\nb\n\n")), "3\n");
+    assert_eq!(stdout(&rdfa(&["-v", "a*"], "baa\nb\n")), "");
+    // but there is nothing to print or highlight for the empty matches
+    assert_eq!(stdout(&rdfa(&["-o", "a*"], "baa\nb\n")), "aa\n");
+    let output = rdfa(&["--color=always", "a*"], "b\n");
+    assert_eq!(stdout(&output), "b\n");
+}
+
+#[test]
+fn test_highlighting() {
+    let output = rdfa(&["--color=always", "aba"], "abababa\n");
+    assert_eq!(
+        stdout(&output),
+        "\x1b[1;31maba\x1b[0mb\x1b[1;31maba\x1b[0m\n"
+    );
+    // non-ASCII input is highlighted at char boundaries
+    let output = rdfa(&["--color=always", "ö+"], "schööön\n");
+    assert_eq!(stdout(&output), "sch\x1b[1;31mööö\x1b[0mn\n");
+    // not a terminal
+    let output = rdfa(&["aba"], "abababa\n");
+    assert_eq!(stdout(&output), "abababa\n");
+}
+
+#[test]
+fn test_files() {
+    let dir = directory(
+        "files",
+        &[("a.txt", "one\ntwo\n"), ("sub/b.txt", "three\n")],
+    );
+    let a = dir.join("a.txt");
+    let b = dir.join("sub/b.txt");
+
+    let output = rdfa(&["-n", "t", a.to_str().unwrap()], "");
+    assert_eq!(stdout(&output), "2:two\n");
+
+    let output = rdfa(&["t", a.to_str().unwrap(), b.to_str().unwrap()], "");
+    assert_eq!(
+        stdout(&output),
+        format!("{}:two\n{}:three\n", a.display(), b.display())
+    );
+
+    let output = rdfa(&["-c", "o", "-", a.to_str().unwrap()], "no\n");
+    assert_eq!(
+        stdout(&output),
+        format!("(standard input):1\n{}:2\n", a.display())
+    );
+
+    let output = rdfa(&["-r", "e", dir.to_str().unwrap()], "");
+    assert_eq!(
+        stdout(&output),
+        format!("{}:one\n{}:three\n", a.display(), b.display())
+    );
+
+    let output = rdfa(&["e", dir.to_str().unwrap()], "");
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = rdfa(&["e", dir.join("missing").to_str().unwrap()], "");
+    assert_eq!(output.status.code(), Some(2));
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_invalid_pattern() {
+    let output = rdfa(&["a|(b"], TEXT);
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "rdfa: invalid pattern: Unclosed '(' at 2..3\n  a|(b\n    ^\n"
+    );
+}
@@ -0,0 +1,156 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Feed `script` to `rdfa repl` and return everything it printed
+fn repl(script: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rdfa"))
+        .arg("repl")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(script.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_words() {
+    assert_eq!(
+        repl("a(b|c)*\nabcb\nba\n\"\"\n\n:p a*\n\"\"\n"),
+        "Nfa with 8 states, Dfa with 4 states, 2 of which are needed\n\
+         accepted\nrejected\nrejected\n\
+         Nfa with 3 states, Dfa with 2 states, 1 of which are needed\n\
+         accepted\n"
+    );
+}
+
+#[test]
+fn test_examples() {
+    let output = repl(
+        "~(a*)&(a|b)*\n:examples 3\n:p ab|c\n:examples\n:p a*\n:examples 2\n:p a&b\n:examples\n",
+    );
+    let examples: Vec<&str> = output
+        .lines()
+        .filter(|l| !l.starts_with("Nfa with"))
+        .collect();
+    assert_eq!(
+        examples,
+        [
+            "b",
+            "ab",
+            "ba",
+            "c",
+            "ab",
+            "\"\"",
+            "a",
+            "No word is accepted"
+        ]
+    );
+}
+
+#[test]
+fn test_equiv() {
+    assert_eq!(
+        repl("a(b|c)*\n:equiv a(c|b)*\n:equiv ab*\n:equiv a(b|c|d)*\n")
+            .lines()
+            .skip(1)
+            .collect::<Vec<_>>(),
+        [
+            "equivalent",
+            "not equivalent, \"ac\" is only accepted by the pattern",
+            "not equivalent, \"ad\" is only accepted by a(b|c|d)*",
+        ]
+    );
+}
+
+#[test]
+fn test_lint() {
+    assert_eq!(
+        repl("ab|a(b|c)\n:lint\n:p a?b*\n:lint\n")
+            .lines()
+            .collect::<Vec<_>>(),
+        [
+            "Nfa with 12 states, Dfa with 4 states, 3 of which are needed",
+            "warning: `ab` is already matched by `a(b|c)`",
+            "  ab|a(b|c)",
+            "  ^^",
+            "Nfa with 7 states, Dfa with 3 states, 2 of which are needed",
+            "warning: The pattern matches the empty word, as every part of it is optional",
+            "  a?b*",
+            "  ^^^^",
+        ]
+    );
+    assert_eq!(
+        repl("ab\n:lint\n").lines().last(),
+        Some("Nothing to complain about")
+    );
+    assert_eq!(
+        repl("a*\n:lint\n").lines().last(),
+        Some("Nothing to complain about")
+    );
+}
+
+#[test]
+fn test_ambiguity() {
+    assert_eq!(
+        repl("(a+)+\n:ambiguity\n:p a*a*\n:ambiguity\n:p ab*\n:ambiguity\n")
+            .lines()
+            .filter(|l| !l.starts_with("Nfa with"))
+            .collect::<Vec<_>>(),
+        [
+            "exponential, words like \"a\" + n * \"a\" + \"!\" take a backtracking engine forever to reject",
+            "polynomial of degree 1, words like \"a\" + n * \"a\" + \"!\" take a backtracking engine long to reject",
+            "bounded, safe for backtracking engines",
+        ]
+    );
+}
+
+#[test]
+fn test_automata() {
+    let output = repl("ab|cb\n:nfa\n:dfa\n:min\n:dot nfa\n:dot\n:dot min\n");
+    assert!(output.contains("Nfa {"));
+    assert_eq!(output.matches("digraph").count(), 3);
+    assert!(output.contains("digraph Nfa"));
+    // states of the Nfa are labelled with the part of the pattern they were created for
+    assert!(output.contains("xlabel=\"ab|cb\""));
+    assert!(output.contains("xlabel=\"c\""));
+}
+
+#[test]
+fn test_never_panics() {
+    let output = repl(
+        "x\n:nfa\n:min\n:bogus\n:examples many\n:dot everything\n:p\n:p (a|\n:equiv )\n:p ~\n**\n\
+         :help\n:quit\nnever read\n",
+    );
+    assert!(!output.contains("never read"));
+    let errors: Vec<&str> = output
+        .lines()
+        .filter(|l| l.starts_with("error: "))
+        .collect();
+    assert_eq!(
+        errors,
+        [
+            "error: Unknown command \":bogus\", :help lists all commands",
+            "error: \"many\" is not a number of examples",
+            "error: Unknown automaton \"everything\", expected nfa, dfa or min",
+            "error: invalid pattern: Empty pattern at 0..0",
+            "error: invalid pattern: Empty alternative at 3..3",
+            "error: invalid pattern: Unexpected ')' at 0..1",
+            "error: invalid pattern: Nothing to complement at 0..1",
+        ]
+    );
+    // before the first pattern, commands have nothing to work with
+    assert_eq!(
+        repl(":dfa\n:equiv a\n"),
+        "error: There is no pattern yet, enter one first\n".repeat(2)
+    );
+}